@@ -32,6 +32,11 @@ impl TyVector3 {
     pub fn magnitude(&self) -> f64 {
         (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
     }
+
+    /// Returns this vector scaled to unit length.
+    pub fn normalized(&self) -> Self {
+        *self * (1.0 / self.magnitude())
+    }
 }
 
 impl Add for TyVector3 {
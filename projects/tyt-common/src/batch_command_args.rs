@@ -0,0 +1,47 @@
+use clap::Command;
+use std::path::Path;
+
+/// Returns the arguments for running a batch's `command` on `input`: the command name, the
+/// input, the command's options, and then `out_base` when given.
+///
+/// `out_base` is only passed to subcommands of `cli` whose last positional argument is an
+/// optional `out_base` following only required positionals, so it can't land in another slot.
+/// Fails for any other subcommand when `out_base` is given.
+pub fn batch_command_args(
+    cli: &Command,
+    command: &[String],
+    input: &Path,
+    out_base: Option<String>,
+) -> Result<Vec<String>, String> {
+    let mut args = vec![command[0].clone(), input.to_string_lossy().into_owned()];
+    args.extend(command[1..].iter().cloned());
+    if let Some(out_base) = out_base {
+        if !cli
+            .find_subcommand(&command[0])
+            .is_some_and(takes_trailing_out_base)
+        {
+            return Err(format!(
+                "`{}` has no output base argument, so it can't be used with `--out-dir`",
+                command[0]
+            ));
+        }
+        // After `--`, the output base fills the next free positional, which is `out_base`.
+        args.push("--".to_owned());
+        args.push(out_base);
+    }
+    Ok(args)
+}
+
+/// Returns `true` if `command`'s last positional argument is an optional `out_base` and every
+/// positional before it is required.
+fn takes_trailing_out_base(command: &Command) -> bool {
+    let positionals: Vec<_> = command.get_positionals().collect();
+    match positionals.split_last() {
+        Some((last, rest)) => {
+            last.get_id() == "out_base"
+                && !last.is_required_set()
+                && rest.iter().all(|arg| arg.is_required_set())
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,12 @@
+use std::path::Path;
+
+/// Expands a batch output directory template for `input`, replacing `{dir}` with the input's
+/// directory (`.` when it has none) and `{stem}` with `stem`.
+pub fn batch_out_dir(template: &str, input: &Path, stem: &str) -> String {
+    let dir = input
+        .parent()
+        .map(|dir| dir.to_string_lossy())
+        .filter(|dir| !dir.is_empty())
+        .unwrap_or(".".into());
+    template.replace("{dir}", &dir).replace("{stem}", stem)
+}
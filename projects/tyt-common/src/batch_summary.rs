@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+/// Formats the outcome of running a command once per input, listing the inputs that succeeded
+/// and then those that failed along with their errors.
+pub fn batch_summary<I: Display, E: Display>(inputs: &[I], results: &[Result<(), E>]) -> String {
+    let total = inputs.len();
+    let succeeded: Vec<&I> = inputs
+        .iter()
+        .zip(results)
+        .filter_map(|(input, result)| result.is_ok().then_some(input))
+        .collect();
+    let failed: Vec<(&I, &E)> = inputs
+        .iter()
+        .zip(results)
+        .filter_map(|(input, result)| result.as_ref().err().map(|e| (input, e)))
+        .collect();
+
+    let mut summary = format!("Succeeded: {} of {total}\n", succeeded.len());
+    for input in succeeded {
+        summary += &format!("  {input}\n");
+    }
+    if !failed.is_empty() {
+        summary += &format!("Failed: {} of {total}\n", failed.len());
+        for (input, e) in failed {
+            let e = e.to_string().replace('\n', "\n    ");
+            summary += &format!("  {input}: {e}\n");
+        }
+    }
+    summary
+}
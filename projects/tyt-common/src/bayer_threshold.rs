@@ -0,0 +1,17 @@
+/// Returns the Bayer matrix entry at `(x, y)` for a matrix `2^bits` on a side, as a threshold in
+/// `(0, 1)`.
+///
+/// Neighboring entries are as far apart as possible, so thresholding a flat color with the
+/// matrix gives an even crosshatch rather than clumps.
+pub fn bayer_threshold(x: u32, y: u32, bits: u32) -> f32 {
+    let mask = (1 << bits) - 1;
+    let (x, y) = (x & mask, y & mask);
+    let xy = x ^ y;
+    // Interleave the bits of `x ^ y` and `y`, least significant first, so the low bits that
+    // alternate between neighbors become the high bits of the threshold.
+    let mut value = 0;
+    for bit in 0..bits {
+        value = (value << 2) | (((xy >> bit) & 1) << 1) | ((y >> bit) & 1);
+    }
+    (value as f32 + 0.5) / (1 << (2 * bits)) as f32
+}
@@ -0,0 +1,45 @@
+/// Decodes an sRGB-encoded channel value in `[0, 1]` to linear light.
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encodes a linear-light channel value as sRGB.
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Converts an sRGB8 color to linear light.
+pub fn srgb8_to_linear(color: [u8; 3]) -> [f32; 3] {
+    color.map(|c| srgb_to_linear(c as f32 / 255.0))
+}
+
+/// Converts a linear-light color to sRGB8, clamping each channel to `[0, 1]`.
+pub fn linear_to_srgb8(color: [f32; 3]) -> [u8; 3] {
+    color.map(|c| (linear_to_srgb(c.clamp(0.0, 1.0)) * 255.0).round() as u8)
+}
+
+/// Converts a linear-light color to Oklab, a perceptual space where Euclidean distance tracks
+/// how different colors look.
+pub fn linear_to_oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// Returns the squared Euclidean distance between two colors.
+pub fn distance_squared(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|c| (a[c] - b[c]) * (a[c] - b[c])).sum()
+}
@@ -0,0 +1,200 @@
+use crate::{
+    TextureFormat, TextureLevels, invalid_data,
+    le_bytes::{read_slice, read_u32_le},
+};
+use std::io::Result;
+
+const MAGIC: &[u8; 4] = b"DDS ";
+const HEADER_SIZE: u32 = 124;
+const PIXEL_FORMAT_SIZE: u32 = 32;
+const DATA_OFFSET: usize = 4 + HEADER_SIZE as usize;
+const DX10_HEADER_SIZE: usize = 20;
+
+const DDSD_CAPS: u32 = 0x1;
+const DDSD_HEIGHT: u32 = 0x2;
+const DDSD_WIDTH: u32 = 0x4;
+const DDSD_PITCH: u32 = 0x8;
+const DDSD_PIXELFORMAT: u32 = 0x1000;
+const DDSD_MIPMAPCOUNT: u32 = 0x2_0000;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDSCAPS_COMPLEX: u32 = 0x8;
+const DDSCAPS_TEXTURE: u32 = 0x1000;
+const DDSCAPS_MIPMAP: u32 = 0x40_0000;
+const DDSCAPS2_CUBEMAP_ALL_FACES: u32 = 0xfe00;
+const DDS_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+const D3D10_RESOURCE_DIMENSION_TEXTURE2D: u32 = 3;
+
+const FOURCC_DX10: u32 = u32::from_le_bytes(*b"DX10");
+const D3DFMT_A16B16G16R16F: u32 = 113;
+const D3DFMT_A32B32G32R32F: u32 = 116;
+
+const DXGI_FORMAT_R32G32B32A32_FLOAT: u32 = 2;
+const DXGI_FORMAT_R16G16B16A16_FLOAT: u32 = 10;
+const DXGI_FORMAT_R8G8B8A8_UNORM: u32 = 28;
+const DXGI_FORMAT_R8G8B8A8_UNORM_SRGB: u32 = 29;
+
+/// Returns `true` if `bytes` starts with the DDS magic number.
+pub fn is_dds(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Encodes a 2D texture or cubemap mip chain as a DDS file with a DX10 header.
+pub fn encode_dds(texture: &TextureLevels) -> Vec<u8> {
+    let format = texture.format;
+    let levels = texture.levels.len() as u32;
+    let cube = texture.faces == 6;
+    let (mip_flag, mip_caps) = if levels > 1 {
+        (DDSD_MIPMAPCOUNT, DDSCAPS_MIPMAP)
+    } else {
+        (0, 0)
+    };
+    let complex = if levels > 1 || cube {
+        DDSCAPS_COMPLEX
+    } else {
+        0
+    };
+
+    let mut out = Vec::new();
+    out.extend(MAGIC);
+    for field in [
+        HEADER_SIZE,
+        DDSD_CAPS | DDSD_HEIGHT | DDSD_WIDTH | DDSD_PITCH | DDSD_PIXELFORMAT | mip_flag,
+        texture.height,
+        texture.width,
+        texture.width * format.texel_size() as u32, // pitchOrLinearSize
+        0,                                          // depth
+        levels,
+    ] {
+        out.extend(field.to_le_bytes());
+    }
+    out.extend([0; 4 * 11]); // reserved1
+    for field in [PIXEL_FORMAT_SIZE, DDPF_FOURCC, FOURCC_DX10, 0, 0, 0, 0, 0] {
+        out.extend(field.to_le_bytes());
+    }
+    for field in [
+        DDSCAPS_TEXTURE | complex | mip_caps,
+        if cube { DDSCAPS2_CUBEMAP_ALL_FACES } else { 0 },
+        0, // caps3
+        0, // caps4
+        0, // reserved2
+    ] {
+        out.extend(field.to_le_bytes());
+    }
+    for field in [
+        dxgi_format(format),
+        D3D10_RESOURCE_DIMENSION_TEXTURE2D,
+        if cube {
+            DDS_RESOURCE_MISC_TEXTURECUBE
+        } else {
+            0
+        },
+        1, // arraySize
+        0, // miscFlags2
+    ] {
+        out.extend(field.to_le_bytes());
+    }
+
+    // DDS stores each face's full mip chain before moving on to the next face.
+    for face in 0..texture.faces {
+        for (level, texels) in texture.levels.iter().enumerate() {
+            let length = texture.face_length(level);
+            out.extend(&texels[face * length..(face + 1) * length]);
+        }
+    }
+    out
+}
+
+/// Decodes a DDS 2D texture or cubemap.
+///
+/// Only uncompressed RGBA8, RGBA16F, and RGBA32F textures are supported, with either a DX10 or a
+/// legacy header.
+pub fn decode_dds(bytes: &[u8]) -> Result<TextureLevels> {
+    if !is_dds(bytes) || read_u32_le(bytes, 4)? != HEADER_SIZE {
+        return Err(invalid_data("not a DDS file"));
+    }
+    let flags = read_u32_le(bytes, 8)?;
+    let height = read_u32_le(bytes, 12)?;
+    let width = read_u32_le(bytes, 16)?;
+    let levels = match read_u32_le(bytes, 28)? {
+        count if flags & DDSD_MIPMAPCOUNT != 0 && count > 0 => count,
+        _ => 1,
+    };
+    if levels > TextureLevels::max_level_count(width, height) {
+        return Err(invalid_data(format!(
+            "DDS mip map count {levels} is too many for {width}x{height}"
+        )));
+    }
+    let pixel_flags = read_u32_le(bytes, 80)?;
+    let four_cc = read_u32_le(bytes, 84)?;
+    let faces = match read_u32_le(bytes, 112)? & DDSCAPS2_CUBEMAP_ALL_FACES {
+        0 => 1,
+        DDSCAPS2_CUBEMAP_ALL_FACES => 6,
+        _ => return Err(invalid_data("DDS cubemaps missing faces are not supported")),
+    };
+
+    let (format, mut offset) = if pixel_flags & DDPF_FOURCC != 0 && four_cc == FOURCC_DX10 {
+        let format = match read_u32_le(bytes, DATA_OFFSET)? {
+            DXGI_FORMAT_R8G8B8A8_UNORM => TextureFormat::Rgba8Unorm,
+            DXGI_FORMAT_R8G8B8A8_UNORM_SRGB => TextureFormat::Rgba8Srgb,
+            DXGI_FORMAT_R16G16B16A16_FLOAT => TextureFormat::Rgba16Float,
+            DXGI_FORMAT_R32G32B32A32_FLOAT => TextureFormat::Rgba32Float,
+            dxgi_format => {
+                return Err(invalid_data(format!(
+                    "unsupported DDS DXGI format {dxgi_format}"
+                )));
+            }
+        };
+        if read_u32_le(bytes, DATA_OFFSET + 12)? > 1 {
+            return Err(invalid_data("DDS texture arrays are not supported"));
+        }
+        (format, DATA_OFFSET + DX10_HEADER_SIZE)
+    } else if pixel_flags & DDPF_FOURCC != 0 {
+        let format = match four_cc {
+            D3DFMT_A16B16G16R16F => TextureFormat::Rgba16Float,
+            D3DFMT_A32B32G32R32F => TextureFormat::Rgba32Float,
+            _ => {
+                return Err(invalid_data(format!(
+                    "unsupported DDS four-character code {four_cc:#x}"
+                )));
+            }
+        };
+        (format, DATA_OFFSET)
+    } else if pixel_flags & DDPF_RGB != 0
+        && read_u32_le(bytes, 88)? == 32
+        && read_u32_le(bytes, 92)? == 0xff
+        && read_u32_le(bytes, 96)? == 0xff00
+        && read_u32_le(bytes, 100)? == 0xff_0000
+    {
+        // Legacy RGBA8 files carry no color space, so they are assumed to be sRGB encoded.
+        (TextureFormat::Rgba8Srgb, DATA_OFFSET)
+    } else {
+        return Err(invalid_data("unsupported DDS pixel format"));
+    };
+
+    let mut texture = TextureLevels {
+        format,
+        width,
+        height,
+        faces,
+        levels: vec![Vec::new(); levels as usize],
+    };
+    for _ in 0..faces {
+        for level in 0..levels as usize {
+            let length = texture.face_length(level);
+            let texels = read_slice(bytes, offset, length)?;
+            texture.levels[level].extend(texels);
+            offset += length;
+        }
+    }
+    Ok(texture)
+}
+
+fn dxgi_format(format: TextureFormat) -> u32 {
+    match format {
+        TextureFormat::Rgba8Unorm => DXGI_FORMAT_R8G8B8A8_UNORM,
+        TextureFormat::Rgba8Srgb => DXGI_FORMAT_R8G8B8A8_UNORM_SRGB,
+        TextureFormat::Rgba16Float => DXGI_FORMAT_R16G16B16A16_FLOAT,
+        TextureFormat::Rgba32Float => DXGI_FORMAT_R32G32B32A32_FLOAT,
+    }
+}
@@ -0,0 +1,70 @@
+use clap::ValueEnum;
+
+/// How colors between palette entries are approximated when reducing to a palette.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Dither {
+    /// Map each pixel to its nearest palette color, leaving flat bands.
+    #[default]
+    None,
+
+    /// Ordered dithering with a 2x2 Bayer matrix, the coarsest pattern.
+    Bayer2,
+
+    /// Ordered dithering with a 4x4 Bayer matrix.
+    Bayer4,
+
+    /// Ordered dithering with an 8x8 Bayer matrix, the finest pattern.
+    Bayer8,
+
+    /// Floyd-Steinberg error diffusion, spreading all of each pixel's error to four neighbors.
+    FloydSteinberg,
+
+    /// Atkinson error diffusion, spreading three quarters of each pixel's error to six
+    /// neighbors for higher contrast.
+    Atkinson,
+}
+
+impl Dither {
+    /// Returns the Bayer matrix size for ordered dithering, as bits per side for
+    /// [`bayer_threshold`](crate::bayer_threshold).
+    pub fn bayer_bits(self) -> Option<u32> {
+        match self {
+            Dither::Bayer2 => Some(1),
+            Dither::Bayer4 => Some(2),
+            Dither::Bayer8 => Some(3),
+            _ => None,
+        }
+    }
+
+    /// Returns the weights for error diffusion.
+    pub fn kernel(self) -> Option<&'static [DiffusionWeight]> {
+        match self {
+            Dither::FloydSteinberg => Some(&FLOYD_STEINBERG),
+            Dither::Atkinson => Some(&ATKINSON),
+            _ => None,
+        }
+    }
+}
+
+/// One neighbor's share of a pixel's error as `(forward, down, weight)`, where `forward` follows
+/// the scan direction of the current row.
+pub type DiffusionWeight = (i64, i64, f32);
+
+/// The most neighbors any [`Dither::kernel`] spreads error to.
+pub const MAX_DIFFUSION_NEIGHBORS: usize = 6;
+
+const FLOYD_STEINBERG: [DiffusionWeight; 4] = [
+    (1, 0, 7.0 / 16.0),
+    (-1, 1, 3.0 / 16.0),
+    (0, 1, 5.0 / 16.0),
+    (1, 1, 1.0 / 16.0),
+];
+
+const ATKINSON: [DiffusionWeight; MAX_DIFFUSION_NEIGHBORS] = [
+    (1, 0, 1.0 / 8.0),
+    (2, 0, 1.0 / 8.0),
+    (-1, 1, 1.0 / 8.0),
+    (0, 1, 1.0 / 8.0),
+    (1, 1, 1.0 / 8.0),
+    (0, 2, 1.0 / 8.0),
+];
@@ -0,0 +1,95 @@
+use clap::ValueEnum;
+use std::path::Path;
+
+/// An image file format that commands read and write.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum ImageFormat {
+    /// 8-bit PNG.
+    #[default]
+    Png,
+
+    /// JPEG, without alpha.
+    Jpg,
+
+    /// Lossless WebP.
+    Webp,
+
+    /// Truevision TGA.
+    Tga,
+
+    /// Windows bitmap.
+    Bmp,
+
+    /// TIFF.
+    Tiff,
+
+    /// Quite OK Image format.
+    Qoi,
+
+    /// GIF, limited to 256 colors.
+    Gif,
+
+    /// Radiance RGBE (`.hdr`), stored at full float precision without alpha.
+    Hdr,
+
+    /// OpenEXR, stored at full float precision.
+    Exr,
+}
+
+impl ImageFormat {
+    /// All formats, in the order they are tried when detecting an input file.
+    pub const ALL: [ImageFormat; 10] = [
+        ImageFormat::Png,
+        ImageFormat::Hdr,
+        ImageFormat::Exr,
+        ImageFormat::Jpg,
+        ImageFormat::Webp,
+        ImageFormat::Tga,
+        ImageFormat::Bmp,
+        ImageFormat::Tiff,
+        ImageFormat::Qoi,
+        ImageFormat::Gif,
+    ];
+
+    /// Returns the file extension written for this format, without a leading dot.
+    pub fn extension(self) -> &'static str {
+        self.extensions()[0]
+    }
+
+    /// Returns every file extension recognized for this format, starting with
+    /// [`ImageFormat::extension`].
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            ImageFormat::Png => &["png"],
+            ImageFormat::Jpg => &["jpg", "jpeg"],
+            ImageFormat::Webp => &["webp"],
+            ImageFormat::Tga => &["tga"],
+            ImageFormat::Bmp => &["bmp"],
+            ImageFormat::Tiff => &["tiff", "tif"],
+            ImageFormat::Qoi => &["qoi"],
+            ImageFormat::Gif => &["gif"],
+            ImageFormat::Hdr => &["hdr"],
+            ImageFormat::Exr => &["exr"],
+        }
+    }
+
+    /// Returns `true` if this format stores linear floating-point color.
+    pub fn is_hdr(self) -> bool {
+        matches!(self, ImageFormat::Hdr | ImageFormat::Exr)
+    }
+
+    /// Returns the format whose extensions include `ext`, ignoring case.
+    pub fn from_extension(ext: &str) -> Option<ImageFormat> {
+        ImageFormat::ALL.into_iter().find(|format| {
+            format
+                .extensions()
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(ext))
+        })
+    }
+
+    /// Returns the format matching `path`'s extension, if it is recognized.
+    pub fn from_path(path: &Path) -> Option<ImageFormat> {
+        ImageFormat::from_extension(path.extension()?.to_str()?)
+    }
+}
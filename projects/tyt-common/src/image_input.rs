@@ -0,0 +1,52 @@
+use crate::{ImageFormat, split_extension};
+use std::io::{Error as IOError, ErrorKind};
+
+/// An input image, given on the command line as either its path or a base name.
+#[derive(Clone, Debug)]
+pub struct ImageInput {
+    /// The file to read.
+    pub path: String,
+
+    /// The path without its extension, used to name outputs.
+    pub base: String,
+
+    pub format: ImageFormat,
+}
+
+impl ImageInput {
+    /// Locates `input`, using `file_exists` to probe paths. An existing file with a recognized
+    /// image extension is used as is; otherwise `input` is a base name and the first existing
+    /// `{input}.{ext}` is used, trying [`ImageFormat::ALL`] in order.
+    pub fn locate<E: From<IOError>>(
+        input: &str,
+        mut file_exists: impl FnMut(&str) -> Result<bool, E>,
+    ) -> Result<Self, E> {
+        if let Some((base, extension)) = split_extension(input)
+            && let Some(format) = ImageFormat::from_extension(extension)
+            && file_exists(input)?
+        {
+            return Ok(Self {
+                path: input.to_owned(),
+                base: base.to_owned(),
+                format,
+            });
+        }
+        for format in ImageFormat::ALL {
+            for extension in format.extensions() {
+                let path = format!("{input}.{extension}");
+                if file_exists(&path)? {
+                    return Ok(Self {
+                        path,
+                        base: input.to_owned(),
+                        format,
+                    });
+                }
+            }
+        }
+        Err(IOError::new(
+            ErrorKind::NotFound,
+            format!("no image found at `{input}` or `{input}.*`"),
+        )
+        .into())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{ImageFormat, invalid_data, split_extension};
+use std::io::{Error as IOError, Result};
+
+/// Where output images are written: `{base}.{extension}` for a single image, or
+/// `{base}-{face}.{extension}` for separate cube faces.
+#[derive(Clone, Debug)]
+pub struct ImageOutput {
+    pub base: String,
+    pub extension: String,
+    pub format: ImageFormat,
+}
+
+impl ImageOutput {
+    /// Creates an output at `{base}.{ext}` for `format`'s usual extension.
+    pub fn new(base: impl Into<String>, format: ImageFormat) -> Self {
+        Self {
+            base: base.into(),
+            extension: format.extension().to_owned(),
+            format,
+        }
+    }
+
+    /// Returns an output at the explicit `output` path when given, failing if its extension is
+    /// not a recognized image format. Otherwise returns [`ImageOutput::new`] for `base` and
+    /// `format`.
+    pub fn resolve(output: Option<&str>, base: String, format: ImageFormat) -> Result<Self> {
+        let Some(output) = output else {
+            return Ok(Self::new(base, format));
+        };
+        let (base, extension) = split_extension(output)
+            .ok_or_else(|| invalid_data::<IOError>(format!("`{output}` has no file extension")))?;
+        let format = ImageFormat::from_extension(extension).ok_or_else(|| {
+            invalid_data::<IOError>(format!(
+                "`{output}` is not a supported image file extension"
+            ))
+        })?;
+        Ok(Self {
+            base: base.to_owned(),
+            extension: extension.to_owned(),
+            format,
+        })
+    }
+
+    /// Returns the path of the single output image.
+    pub fn path(&self) -> String {
+        format!("{}.{}", self.base, self.extension)
+    }
+
+    /// Returns this output with `suffix` appended to its base name.
+    pub fn with_suffix(&self, suffix: &str) -> Self {
+        Self {
+            base: format!("{}{suffix}", self.base),
+            ..self.clone()
+        }
+    }
+}
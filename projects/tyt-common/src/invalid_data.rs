@@ -0,0 +1,7 @@
+use std::io::{Error as IOError, ErrorKind};
+
+/// Returns an [`ErrorKind::InvalidData`] error with `message`, as any error type that wraps
+/// [`IOError`].
+pub fn invalid_data<E: From<IOError>>(message: impl Into<String>) -> E {
+    IOError::new(ErrorKind::InvalidData, message.into()).into()
+}
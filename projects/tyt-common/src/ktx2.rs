@@ -0,0 +1,218 @@
+use crate::{
+    TextureFormat, TextureLevels, invalid_data,
+    le_bytes::{read_slice, read_u32_le, read_u64_le},
+};
+use std::io::Result;
+
+const IDENTIFIER: [u8; 12] = [
+    0xab, 0x4b, 0x54, 0x58, 0x20, 0x32, 0x30, 0xbb, 0x0d, 0x0a, 0x1a, 0x0a,
+];
+const HEADER_SIZE: usize = 80;
+const LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+
+const VK_FORMAT_R8G8B8A8_UNORM: u32 = 37;
+const VK_FORMAT_R8G8B8A8_SRGB: u32 = 43;
+const VK_FORMAT_R16G16B16A16_SFLOAT: u32 = 97;
+const VK_FORMAT_R32G32B32A32_SFLOAT: u32 = 109;
+
+/// Returns `true` if `bytes` starts with the KTX2 file identifier.
+pub fn is_ktx2(bytes: &[u8]) -> bool {
+    bytes.starts_with(&IDENTIFIER)
+}
+
+/// Encodes a 2D texture or cubemap mip chain as a KTX2 file.
+pub fn encode_ktx2(texture: &TextureLevels) -> Vec<u8> {
+    let format = texture.format;
+    let levels = texture.levels.len();
+    let dfd = data_format_descriptor(format);
+    let kvd = key_value_data(&[("KTXwriter", "tyt")]);
+
+    let dfd_offset = HEADER_SIZE + LEVEL_INDEX_ENTRY_SIZE * levels;
+    let kvd_offset = dfd_offset + dfd.len();
+    let alignment = format.texel_size().max(4);
+
+    // Level data is stored smallest first, while the level index is ordered from level 0.
+    let mut data = Vec::new();
+    let mut level_index = vec![(0, 0); levels];
+    let data_offset = kvd_offset + kvd.len();
+    for (level, texels) in texture.levels.iter().enumerate().rev() {
+        while !(data_offset + data.len()).is_multiple_of(alignment) {
+            data.push(0);
+        }
+        let start = data_offset + data.len();
+        data.extend(texels);
+        level_index[level] = (start as u64, texels.len() as u64);
+    }
+
+    let mut out = Vec::with_capacity(data_offset + data.len());
+    out.extend(IDENTIFIER);
+    for field in [
+        vk_format(format),
+        format.channel_size() as u32, // typeSize
+        texture.width,
+        texture.height,
+        0, // pixelDepth
+        0, // layerCount
+        texture.faces as u32,
+        levels as u32,
+        0, // supercompressionScheme
+        dfd_offset as u32,
+        dfd.len() as u32,
+        kvd_offset as u32,
+        kvd.len() as u32,
+    ] {
+        out.extend(field.to_le_bytes());
+    }
+    out.extend(0u64.to_le_bytes()); // sgdByteOffset
+    out.extend(0u64.to_le_bytes()); // sgdByteLength
+    for (offset, length) in level_index {
+        out.extend(offset.to_le_bytes());
+        out.extend(length.to_le_bytes());
+        out.extend(length.to_le_bytes()); // uncompressedByteLength
+    }
+    out.extend(dfd);
+    out.extend(kvd);
+    out.extend(data);
+    out
+}
+
+/// Decodes a KTX2 2D texture or cubemap.
+///
+/// Only uncompressed RGBA8, RGBA16F, and RGBA32F textures without supercompression are
+/// supported.
+pub fn decode_ktx2(bytes: &[u8]) -> Result<TextureLevels> {
+    if !is_ktx2(bytes) {
+        return Err(invalid_data("not a KTX2 file"));
+    }
+    let format = match read_u32_le(bytes, 12)? {
+        VK_FORMAT_R8G8B8A8_UNORM => TextureFormat::Rgba8Unorm,
+        VK_FORMAT_R8G8B8A8_SRGB => TextureFormat::Rgba8Srgb,
+        VK_FORMAT_R16G16B16A16_SFLOAT => TextureFormat::Rgba16Float,
+        VK_FORMAT_R32G32B32A32_SFLOAT => TextureFormat::Rgba32Float,
+        vk_format => {
+            return Err(invalid_data(format!(
+                "unsupported KTX2 vkFormat {vk_format}"
+            )));
+        }
+    };
+    let width = read_u32_le(bytes, 20)?;
+    let height = read_u32_le(bytes, 24)?;
+    let depth = read_u32_le(bytes, 28)?;
+    let layers = read_u32_le(bytes, 32)?;
+    let faces = read_u32_le(bytes, 36)?;
+    let levels = read_u32_le(bytes, 40)?.max(1);
+    let supercompression = read_u32_le(bytes, 44)?;
+    if height == 0 || depth != 0 || layers > 1 || !matches!(faces, 1 | 6) {
+        return Err(invalid_data(
+            "KTX2 file is not a single 2D texture or cubemap",
+        ));
+    }
+    if supercompression != 0 {
+        return Err(invalid_data("supercompressed KTX2 files are not supported"));
+    }
+    if levels > TextureLevels::max_level_count(width, height) {
+        return Err(invalid_data(format!(
+            "KTX2 levelCount {levels} is too many for {width}x{height}"
+        )));
+    }
+
+    let mut texture = TextureLevels {
+        format,
+        width,
+        height,
+        faces: faces as usize,
+        levels: Vec::with_capacity(levels as usize),
+    };
+    for level in 0..levels as usize {
+        let entry = HEADER_SIZE + LEVEL_INDEX_ENTRY_SIZE * level;
+        let offset = read_u64_le(bytes, entry)? as usize;
+        let length = read_u64_le(bytes, entry + 8)? as usize;
+        let expected = texture.face_length(level).saturating_mul(texture.faces);
+        if length != expected {
+            return Err(invalid_data(format!(
+                "KTX2 level {level} is {length} bytes, expected {expected}"
+            )));
+        }
+        texture
+            .levels
+            .push(read_slice(bytes, offset, length)?.to_vec());
+    }
+    Ok(texture)
+}
+
+fn vk_format(format: TextureFormat) -> u32 {
+    match format {
+        TextureFormat::Rgba8Unorm => VK_FORMAT_R8G8B8A8_UNORM,
+        TextureFormat::Rgba8Srgb => VK_FORMAT_R8G8B8A8_SRGB,
+        TextureFormat::Rgba16Float => VK_FORMAT_R16G16B16A16_SFLOAT,
+        TextureFormat::Rgba32Float => VK_FORMAT_R32G32B32A32_SFLOAT,
+    }
+}
+
+/// Builds a Khronos basic data format descriptor for an RGBA texel format.
+fn data_format_descriptor(format: TextureFormat) -> Vec<u8> {
+    const KHR_DF_MODEL_RGBSDA: u8 = 1;
+    const KHR_DF_PRIMARIES_BT709: u8 = 1;
+    const KHR_DF_TRANSFER_LINEAR: u8 = 1;
+    const KHR_DF_TRANSFER_SRGB: u8 = 2;
+    const KHR_DF_SAMPLE_DATATYPE_LINEAR: u8 = 0x10;
+    const KHR_DF_SAMPLE_DATATYPE_SIGNED: u8 = 0x40;
+    const KHR_DF_SAMPLE_DATATYPE_FLOAT: u8 = 0x80;
+    const CHANNEL_IDS: [u8; 4] = [0, 1, 2, 15];
+
+    let block_size = 24 + 16 * CHANNEL_IDS.len();
+    let channel_bits = format.channel_size() * 8;
+    let srgb = format == TextureFormat::Rgba8Srgb;
+    let transfer = if srgb {
+        KHR_DF_TRANSFER_SRGB
+    } else {
+        KHR_DF_TRANSFER_LINEAR
+    };
+    let (lower, upper) = match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8Srgb => (0, 255),
+        TextureFormat::Rgba16Float => (0xbc00, 0x3c00),
+        TextureFormat::Rgba32Float => ((-1.0f32).to_bits(), 1.0f32.to_bits()),
+    };
+
+    let mut out = Vec::with_capacity(4 + block_size);
+    out.extend(((4 + block_size) as u32).to_le_bytes()); // dfdTotalSize
+    out.extend(0u32.to_le_bytes()); // vendorId and descriptorType
+    out.extend(2u16.to_le_bytes()); // versionNumber
+    out.extend((block_size as u16).to_le_bytes());
+    out.extend([KHR_DF_MODEL_RGBSDA, KHR_DF_PRIMARIES_BT709, transfer, 0]);
+    out.extend([0; 4]); // texelBlockDimension
+    out.extend([format.texel_size() as u8, 0, 0, 0, 0, 0, 0, 0]); // bytesPlane
+    for (i, channel) in CHANNEL_IDS.into_iter().enumerate() {
+        // Alpha is always linear, even when the color channels are sRGB.
+        let qualifiers = if format.is_float() {
+            KHR_DF_SAMPLE_DATATYPE_FLOAT | KHR_DF_SAMPLE_DATATYPE_SIGNED
+        } else if srgb && channel == 15 {
+            KHR_DF_SAMPLE_DATATYPE_LINEAR
+        } else {
+            0
+        };
+        out.extend(((i * channel_bits) as u16).to_le_bytes()); // bitOffset
+        out.extend([(channel_bits - 1) as u8, channel | qualifiers]);
+        out.extend([0; 4]); // samplePosition
+        out.extend(lower.to_le_bytes());
+        out.extend(upper.to_le_bytes());
+    }
+    out
+}
+
+/// Builds a key/value data block from `(key, value)` pairs, which must be sorted by key.
+fn key_value_data(pairs: &[(&str, &str)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (key, value) in pairs {
+        let length = key.len() + value.len() + 2;
+        out.extend((length as u32).to_le_bytes());
+        out.extend(key.as_bytes());
+        out.push(0);
+        out.extend(value.as_bytes());
+        out.push(0);
+        while !out.len().is_multiple_of(4) {
+            out.push(0);
+        }
+    }
+    out
+}
@@ -0,0 +1,35 @@
+use std::io::{Error as IOError, ErrorKind, Result};
+
+/// Reads a little-endian `u32` at `offset`, failing if `bytes` is too short.
+pub(crate) fn read_u32_le(bytes: &[u8], offset: usize) -> Result<u32> {
+    let field = bytes
+        .get(offset..offset + 4)
+        .ok_or_else(|| truncated(offset))?;
+    Ok(u32::from_le_bytes(
+        field.try_into().expect("slice is 4 bytes"),
+    ))
+}
+
+/// Reads a little-endian `u64` at `offset`, failing if `bytes` is too short.
+pub(crate) fn read_u64_le(bytes: &[u8], offset: usize) -> Result<u64> {
+    let field = bytes
+        .get(offset..offset + 8)
+        .ok_or_else(|| truncated(offset))?;
+    Ok(u64::from_le_bytes(
+        field.try_into().expect("slice is 8 bytes"),
+    ))
+}
+
+/// Returns the `len` bytes at `offset`, failing if `bytes` is too short.
+pub(crate) fn read_slice(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    bytes
+        .get(offset..offset.saturating_add(len))
+        .ok_or_else(|| truncated(offset))
+}
+
+fn truncated(offset: usize) -> IOError {
+    IOError::new(
+        ErrorKind::UnexpectedEof,
+        format!("file is truncated at byte {offset}"),
+    )
+}
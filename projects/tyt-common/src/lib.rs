@@ -1,3 +1,44 @@
+mod batch_command_args;
+mod batch_out_dir;
+mod batch_summary;
+mod bayer_threshold;
+mod color_space;
+mod dds;
+mod dither;
 mod exec_failed;
+mod image_format;
+mod image_input;
+mod image_output;
+mod invalid_data;
+mod ktx2;
+mod le_bytes;
+mod palette;
+mod palette_mean_spacing;
+mod palette_parsing;
+mod run_parallel;
+mod split_extension;
+mod texture_format;
+mod texture_levels;
+mod usage_error;
 
+pub use batch_command_args::*;
+pub use batch_out_dir::*;
+pub use batch_summary::*;
+pub use bayer_threshold::*;
+pub use color_space::*;
+pub use dds::*;
+pub use dither::*;
 pub use exec_failed::*;
+pub use image_format::*;
+pub use image_input::*;
+pub use image_output::*;
+pub use invalid_data::*;
+pub use ktx2::*;
+pub use palette::*;
+pub use palette_mean_spacing::*;
+pub use palette_parsing::*;
+pub use run_parallel::*;
+pub use split_extension::*;
+pub use texture_format::*;
+pub use texture_levels::*;
+pub use usage_error::*;
@@ -0,0 +1,90 @@
+use crate::{
+    PALETTE_TEXT_EXTENSIONS, distance_squared, invalid_data, linear_to_oklab, palette_image_colors,
+    palette_mean_spacing, parse_palette_text, split_extension, srgb_to_linear, srgb8_to_linear,
+};
+use std::io::Error as IOError;
+
+/// A fixed set of sRGB8 colors to reduce images to.
+#[derive(Clone, Debug)]
+pub struct Palette {
+    pub colors: Vec<[u8; 3]>,
+
+    /// `colors` in Oklab, for matching.
+    oklab: Vec<[f32; 3]>,
+}
+
+impl Palette {
+    /// Creates a palette from sRGB8 colors.
+    pub fn new(colors: Vec<[u8; 3]>) -> Self {
+        let oklab = colors
+            .iter()
+            .map(|&c| linear_to_oklab(srgb8_to_linear(c)))
+            .collect();
+        Self { colors, oklab }
+    }
+
+    /// Loads a palette from `path`, in any format Lospec offers: a text palette (see
+    /// [`parse_palette_text`]) chosen by extension, or any other extension as a swatch image,
+    /// whose distinct opaque colors form the palette in reading order.
+    ///
+    /// `read_file` returns a file's bytes, and `load_image_rgba8` an image's RGBA8 pixels.
+    pub fn load<E: From<IOError>>(
+        path: &str,
+        read_file: impl FnOnce(&str) -> Result<Vec<u8>, E>,
+        load_image_rgba8: impl FnOnce(&str) -> Result<Vec<u8>, E>,
+    ) -> Result<Self, E> {
+        let extension = split_extension(path)
+            .map(|(_, e)| e.to_ascii_lowercase())
+            .unwrap_or_default();
+        let parsed = if PALETTE_TEXT_EXTENSIONS.contains(&extension.as_str()) {
+            parse_palette_text(&extension, &String::from_utf8_lossy(&read_file(path)?))
+        } else {
+            palette_image_colors(&load_image_rgba8(path)?)
+        };
+        let colors = parsed.map_err(|e| invalid_data::<E>(format!("palette `{path}`: {e}")))?;
+        if colors.is_empty() {
+            return Err(invalid_data(format!("palette `{path}` has no colors")));
+        }
+        Ok(Self::new(colors))
+    }
+
+    /// Returns the index of the palette color that looks closest to `color`, given sRGB encoded
+    /// in `[0, 1]`. Colors are compared in Oklab.
+    pub fn nearest(&self, color: [f32; 3]) -> usize {
+        let target = linear_to_oklab(color.map(|c| srgb_to_linear(c.clamp(0.0, 1.0))));
+        let mut best = 0;
+        let mut best_distance = f32::INFINITY;
+        for (i, &candidate) in self.oklab.iter().enumerate() {
+            let distance = distance_squared(target, candidate);
+            if distance < best_distance {
+                best = i;
+                best_distance = distance;
+            }
+        }
+        best
+    }
+
+    /// Returns the palette color that looks closest to `color`, both sRGB encoded in `[0, 1]`.
+    pub fn nearest_color(&self, color: [f32; 3]) -> [f32; 3] {
+        self.colors[self.nearest(color)].map(|c| c as f32 / 255.0)
+    }
+
+    /// Returns the mean sRGB distance from each color to its closest other color, a measure of
+    /// how far apart the palette's steps are.
+    pub fn mean_spacing(&self) -> f32 {
+        let encoded: Vec<[f32; 3]> = self
+            .colors
+            .iter()
+            .map(|c| c.map(|c| c as f32 / 255.0))
+            .collect();
+        palette_mean_spacing(&encoded)
+    }
+
+    /// Returns the palette as a `.hex` file, one `rrggbb` color per line.
+    pub fn to_hex(&self) -> String {
+        self.colors
+            .iter()
+            .map(|[r, g, b]| format!("{r:02x}{g:02x}{b:02x}\n"))
+            .collect()
+    }
+}
@@ -0,0 +1,23 @@
+use crate::distance_squared;
+
+/// Returns the mean distance from each color to its closest other color, a measure of how far
+/// apart a palette's steps are. Colors are sRGB encoded in `[0, 1]`.
+pub fn palette_mean_spacing(colors: &[[f32; 3]]) -> f32 {
+    if colors.len() < 2 {
+        return 0.0;
+    }
+    let total: f32 = colors
+        .iter()
+        .enumerate()
+        .map(|(i, &a)| {
+            colors
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &b)| distance_squared(a, b))
+                .fold(f32::INFINITY, f32::min)
+                .sqrt()
+        })
+        .sum();
+    total / colors.len() as f32
+}
@@ -0,0 +1,114 @@
+/// Most colors a swatch image may hold to be used as a palette.
+pub const MAX_PALETTE_IMAGE_COLORS: usize = 256;
+
+/// Lowercase extensions of the text palette formats read by [`parse_palette_text`]. Palettes
+/// with other extensions are swatch images, read with [`palette_image_colors`].
+pub const PALETTE_TEXT_EXTENSIONS: &[&str] = &["hex", "txt", "gpl", "pal"];
+
+/// Parses the text of a palette file by its lowercase `extension`:
+///
+/// - `hex`: one `rrggbb` color per line.
+/// - `txt`: a Paint.NET palette, with `;` comments and one `aarrggbb` color per line. Plain
+///   `rrggbb` lines are accepted too.
+/// - `gpl`: a GIMP palette.
+/// - `pal`: a JASC (Paint Shop Pro) palette.
+pub fn parse_palette_text(extension: &str, text: &str) -> Result<Vec<[u8; 3]>, String> {
+    match extension {
+        "hex" | "txt" => parse_hex(text),
+        "gpl" => parse_gpl(text),
+        "pal" => parse_jasc(text),
+        _ => Err(format!("`.{extension}` is not a text palette format")),
+    }
+}
+
+/// Returns the distinct opaque colors of RGBA8 `pixels` in reading order, for using a swatch
+/// image as a palette.
+pub fn palette_image_colors(pixels: &[u8]) -> Result<Vec<[u8; 3]>, String> {
+    let mut colors: Vec<[u8; 3]> = Vec::new();
+    for pixel in pixels.chunks_exact(4) {
+        let color = [pixel[0], pixel[1], pixel[2]];
+        if pixel[3] == 0 || colors.contains(&color) {
+            continue;
+        }
+        if colors.len() == MAX_PALETTE_IMAGE_COLORS {
+            return Err(format!(
+                "swatch images may have at most {MAX_PALETTE_IMAGE_COLORS} colors"
+            ));
+        }
+        colors.push(color);
+    }
+    Ok(colors)
+}
+
+/// Parses one `rrggbb`, `#rrggbb`, or `aarrggbb` color per line, skipping blank lines and `;`
+/// comments.
+fn parse_hex(text: &str) -> Result<Vec<[u8; 3]>, String> {
+    let mut colors = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        let hex = line.strip_prefix('#').unwrap_or(line);
+        let parsed = matches!(hex.len(), 6 | 8)
+            .then(|| u32::from_str_radix(hex, 16).ok())
+            .flatten()
+            .ok_or_else(|| format!("line {}: `{line}` is not a hex color", i + 1))?;
+        colors.push([(parsed >> 16) as u8, (parsed >> 8) as u8, parsed as u8]);
+    }
+    Ok(colors)
+}
+
+/// Parses a GIMP palette: a `GIMP Palette` header, optional `Name:` and `Columns:` lines, `#`
+/// comments, and one `red green blue [name]` color per line.
+fn parse_gpl(text: &str) -> Result<Vec<[u8; 3]>, String> {
+    let mut lines = text.lines().enumerate();
+    if lines.next().map(|(_, l)| l.trim()) != Some("GIMP Palette") {
+        return Err("missing `GIMP Palette` header".to_owned());
+    }
+    let mut colors = Vec::new();
+    for (i, line) in lines {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("Name:")
+            || line.starts_with("Columns:")
+        {
+            continue;
+        }
+        colors.push(parse_channels(line).ok_or_else(|| not_a_color(i, line))?);
+    }
+    Ok(colors)
+}
+
+/// Parses a JASC palette: `JASC-PAL`, a version line, a color count, and one `red green blue`
+/// color per line.
+fn parse_jasc(text: &str) -> Result<Vec<[u8; 3]>, String> {
+    let mut lines = text.lines().enumerate();
+    if lines.next().map(|(_, l)| l.trim()) != Some("JASC-PAL") {
+        return Err("missing `JASC-PAL` header".to_owned());
+    }
+    let mut colors = Vec::new();
+    for (i, line) in lines.skip(2) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        colors.push(parse_channels(line).ok_or_else(|| not_a_color(i, line))?);
+    }
+    Ok(colors)
+}
+
+/// Parses the first three whitespace-separated channels of `line`.
+fn parse_channels(line: &str) -> Option<[u8; 3]> {
+    let channels: Vec<u8> = line
+        .split_whitespace()
+        .take(3)
+        .map_while(|c| c.parse().ok())
+        .collect();
+    channels.try_into().ok()
+}
+
+fn not_a_color(index: usize, line: &str) -> String {
+    format!("line {}: `{line}` is not a color", index + 1)
+}
@@ -0,0 +1,8 @@
+use std::path::Path;
+
+/// Splits a path at the dot before its file extension, returning `(path_without_extension,
+/// extension)`.
+pub fn split_extension(path: &str) -> Option<(&str, &str)> {
+    let extension = Path::new(path).extension()?.to_str()?;
+    Some((&path[..path.len() - extension.len() - 1], extension))
+}
@@ -0,0 +1,39 @@
+/// An uncompressed RGBA texel format that KTX2 and DDS containers can hold.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextureFormat {
+    /// 8 bits per channel, linear.
+    Rgba8Unorm,
+
+    /// 8 bits per channel, sRGB-encoded color with linear alpha.
+    Rgba8Srgb,
+
+    /// 16-bit half-float per channel, linear.
+    Rgba16Float,
+
+    /// 32-bit float per channel, linear.
+    Rgba32Float,
+}
+
+impl TextureFormat {
+    /// Returns the size of one texel in bytes.
+    pub fn texel_size(self) -> usize {
+        match self {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8Srgb => 4,
+            TextureFormat::Rgba16Float => 8,
+            TextureFormat::Rgba32Float => 16,
+        }
+    }
+
+    /// Returns the size of one channel in bytes.
+    pub fn channel_size(self) -> usize {
+        self.texel_size() / 4
+    }
+
+    /// Returns `true` for floating-point formats.
+    pub fn is_float(self) -> bool {
+        matches!(
+            self,
+            TextureFormat::Rgba16Float | TextureFormat::Rgba32Float
+        )
+    }
+}
@@ -0,0 +1,45 @@
+use crate::TextureFormat;
+
+/// An uncompressed texture's mip chain, as stored in a KTX2 or DDS container.
+#[derive(Clone, Debug)]
+pub struct TextureLevels {
+    pub format: TextureFormat,
+
+    /// Width of the full-resolution level in texels.
+    pub width: u32,
+
+    /// Height of the full-resolution level in texels.
+    pub height: u32,
+
+    /// Faces in each level: 6 for a cubemap, ordered +X, -X, +Y, -Y, +Z, -Z, or 1 otherwise.
+    pub faces: usize,
+
+    /// Each level's faces one after another, as tightly packed, top-to-bottom rows of texels.
+    /// `levels[0]` is the full-resolution level.
+    pub levels: Vec<Vec<u8>>,
+}
+
+impl TextureLevels {
+    /// Returns the most mip levels a `width` x `height` texture can have, down to 1x1.
+    pub fn max_level_count(width: u32, height: u32) -> u32 {
+        u32::BITS - width.max(height).max(1).leading_zeros()
+    }
+
+    /// Returns the width and height of `level`.
+    pub fn level_size(&self, level: usize) -> (u32, u32) {
+        let shift = u32::try_from(level).unwrap_or(u32::MAX);
+        (
+            self.width.checked_shr(shift).unwrap_or(0).max(1),
+            self.height.checked_shr(shift).unwrap_or(0).max(1),
+        )
+    }
+
+    /// Returns the size in bytes of one face of `level`, saturating rather than overflowing for
+    /// sizes no file could hold.
+    pub fn face_length(&self, level: usize) -> usize {
+        let (width, height) = self.level_size(level);
+        (width as usize)
+            .saturating_mul(height as usize)
+            .saturating_mul(self.format.texel_size())
+    }
+}
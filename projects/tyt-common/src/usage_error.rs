@@ -0,0 +1,12 @@
+use std::fmt::Display;
+
+/// Returns a command-line parse error's message without the `error: ` prefix that the caller
+/// adds again when reporting it.
+pub fn usage_error(e: &impl Display) -> String {
+    let message = e.to_string();
+    message
+        .strip_prefix("error: ")
+        .unwrap_or(&message)
+        .trim_end()
+        .to_owned()
+}
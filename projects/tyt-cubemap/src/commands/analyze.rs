@@ -0,0 +1,63 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming},
+};
+use clap::Parser;
+use tyt_common::ImageInput;
+
+/// Finds the sun and representative sky colors of a cubemap or equirectangular panorama.
+///
+/// Writes `{out_base}.json` (or `--output`) with the sun's direction, intensity, and color, and
+/// the mean zenith (60 degrees and up), horizon (0 to 15 degrees), ground, and ambient colors,
+/// in linear color and the cubemap's right-handed, Y-up frame. Sun texels are left out of the
+/// other colors.
+#[derive(Clone, Debug, Parser)]
+pub struct Analyze {
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts and `--equirect`.
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-analysis`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output JSON path.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
+    /// Read the input as an equirectangular image instead of a cubemap.
+    #[arg(value_name = "equirect", long)]
+    equirect: bool,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+}
+
+impl Analyze {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let (analysis, in_base) = if self.equirect {
+            let input = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+            let equirect = utilities::load_image(&deps, &input.path)?;
+            (utilities::analyze_equirect(&equirect), input.base)
+        } else {
+            let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+            (utilities::analyze_faces(&cube.faces), cube.base)
+        };
+
+        let json_path = self.output.unwrap_or_else(|| {
+            let out_base = self
+                .out_base
+                .unwrap_or_else(|| format!("{in_base}-analysis"));
+            format!("{out_base}.json")
+        });
+        deps.write_file(&json_path, &deps.serialize_sky_analysis_json(&analysis)?)?;
+        deps.write_stdout(format!("Wrote: {json_path}\n").as_bytes())?;
+        Ok(())
+    }
+}
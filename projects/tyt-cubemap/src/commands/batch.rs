@@ -0,0 +1,152 @@
+use crate::{
+    Dependencies, Error, Result, TytCubemap,
+    utilities::{CubeContainer, Face, FaceNaming},
+};
+use clap::{CommandFactory, Parser};
+use std::{
+    io::Error as IOError,
+    path::{Path, PathBuf},
+};
+use tyt_common::{ImageFormat, invalid_data};
+
+/// Runs another cubemap command once for every file matched by a glob or inside a directory.
+///
+/// Each file is passed as the command's input. A file that fails is reported in the summary at
+/// the end without stopping the others.
+#[derive(Clone, Debug, Parser)]
+pub struct Batch {
+    /// Glob pattern (quoted, e.g. `'skies/*.hdr'`) or directory of input files. Match face sets
+    /// by one face each, e.g. `'skies/*-front.png'`.
+    #[arg(value_name = "input")]
+    input: String,
+
+    /// Output directory template, where `{dir}` is the input's directory and `{stem}` its file
+    /// name without the extension (or face suffix). Each command's output base becomes
+    /// `{out-dir}/{stem}`. Only for commands with an output base argument. Defaults to the
+    /// command's usual output beside each input.
+    #[arg(value_name = "out-dir", long)]
+    out_dir: Option<String>,
+
+    /// Number of files to process at once.
+    #[arg(value_name = "jobs", short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// The command to run and its options, e.g. `equirect-to-faces --size 512`.
+    #[arg(
+        value_name = "command",
+        required = true,
+        trailing_var_arg = true,
+        allow_hyphen_values = true
+    )]
+    command: Vec<String>,
+}
+
+/// Parses a cubemap command from arguments without a binary name.
+#[derive(Parser)]
+#[command(name = "batch", no_binary_name = true)]
+struct BatchCommand {
+    #[command(subcommand)]
+    command: TytCubemap,
+}
+
+impl Batch {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let mut inputs = deps.glob_files(&self.input)?;
+        inputs.retain(|path| {
+            ImageFormat::from_path(path).is_some()
+                || path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .and_then(CubeContainer::from_extension)
+                    .is_some()
+        });
+        // A face set only needs to be processed once, through any one of its faces.
+        let mut face_sets = Vec::new();
+        inputs.retain(|path| match face_set(path) {
+            Some(set) if face_sets.contains(&set) => false,
+            Some(set) => {
+                face_sets.push(set);
+                true
+            }
+            None => true,
+        });
+        if inputs.is_empty() {
+            return Err(invalid_data(format!("no files match `{}`", self.input)));
+        }
+
+        // Parse every command up front so usage errors stop the batch before anything runs.
+        let mut commands = Vec::with_capacity(inputs.len());
+        for input in &inputs {
+            let command = BatchCommand::try_parse_from(self.command_args(input)?)
+                .map_err(|e| invalid_data::<Error>(tyt_common::usage_error(&e)))?
+                .command;
+            if matches!(command, TytCubemap::Batch(_)) {
+                return Err(invalid_data("batch commands cannot be nested"));
+            }
+            commands.push((input, command));
+        }
+
+        let results = tyt_common::run_parallel(&commands, self.jobs, |(input, command)| {
+            if let Some(out_dir) = self.out_dir(input) {
+                deps.create_dir_all(out_dir)?;
+            }
+            command.clone().execute(deps.clone())
+        });
+
+        let labels: Vec<_> = inputs.iter().map(|input| input.display()).collect();
+        deps.write_stdout(tyt_common::batch_summary(&labels, &results).as_bytes())?;
+        let failed = results.iter().filter(|result| result.is_err()).count();
+        if failed > 0 {
+            return Err(
+                IOError::other(format!("{failed} of {} files failed", inputs.len())).into(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the expanded output directory for `input`.
+    fn out_dir(&self, input: &Path) -> Option<String> {
+        let template = self.out_dir.as_ref()?;
+        Some(tyt_common::batch_out_dir(
+            template,
+            input,
+            &output_stem(input),
+        ))
+    }
+
+    /// Returns the arguments for running the command on `input`, with the output base under
+    /// `--out-dir` when given.
+    fn command_args(&self, input: &Path) -> Result<Vec<String>> {
+        let out_base = self
+            .out_dir(input)
+            .map(|out_dir| format!("{out_dir}/{}", output_stem(input)));
+        tyt_common::batch_command_args(&BatchCommand::command(), &self.command, input, out_base)
+            .map_err(invalid_data)
+    }
+}
+
+/// Returns `input`'s file name without its extension or, for a face file, its face suffix.
+fn output_stem(input: &Path) -> String {
+    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+    match strip_face_suffix(&stem) {
+        Some((base, _)) => base.to_owned(),
+        None => stem.into_owned(),
+    }
+}
+
+/// Identifies the face set that `input` belongs to, if its name ends in a face suffix.
+fn face_set(input: &Path) -> Option<(PathBuf, FaceNaming)> {
+    let stem = input.file_stem()?.to_str()?;
+    let (base, naming) = strip_face_suffix(stem)?;
+    Some((input.with_file_name(base), naming))
+}
+
+/// Splits a `-{face}` suffix from `stem`, returning the base and the naming scheme it follows.
+fn strip_face_suffix(stem: &str) -> Option<(&str, FaceNaming)> {
+    FaceNaming::ALL.into_iter().find_map(|naming| {
+        Face::ALL.into_iter().find_map(|face| {
+            stem.strip_suffix(&format!("-{}", naming.face_name(face)))
+                .map(|base| (base, naming))
+        })
+    })
+}
@@ -0,0 +1,51 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming},
+};
+use clap::Parser;
+
+/// Scores the color discontinuity along each of the 12 cube edges.
+///
+/// For each edge, prints the mean and largest difference across the seam, and the mean
+/// difference between border texels and their inward neighbors. A seam whose mean is well above
+/// its interior value is likely visible.
+#[derive(Clone, Debug, Parser)]
+pub struct CheckSeams {
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts.
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+}
+
+impl CheckSeams {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let faces = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?.faces;
+
+        let mut report = String::new();
+        let mut worst: Option<(String, f32)> = None;
+        for seam in utilities::cube_seams(faces[0].width) {
+            let score = utilities::score_seam(&faces, &seam);
+            let name = format!("{}/{}", seam.faces.0.name(), seam.faces.1.name());
+            report += &format!(
+                "{name:<12} mean {:.4}  max {:.4}  interior {:.4}\n",
+                score.mean, score.max, score.interior
+            );
+            if worst.as_ref().is_none_or(|(_, mean)| score.mean > *mean) {
+                worst = Some((name, score.mean));
+            }
+        }
+        if let Some((name, mean)) = worst {
+            report += &format!("Worst seam: {name} (mean {mean:.4})\n");
+        }
+        deps.write_stdout(report.as_bytes())?;
+        Ok(())
+    }
+}
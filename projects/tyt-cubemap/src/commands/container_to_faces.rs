@@ -0,0 +1,99 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeContainer, CubeLayout, FaceNaming},
+};
+use clap::Parser;
+use std::path::PathBuf;
+use tyt_common::{ImageFormat, ImageOutput, invalid_data};
+
+/// Extracts cube faces from a KTX2 or DDS cubemap file.
+#[derive(Clone, Debug, Parser)]
+pub struct ContainerToFaces {
+    /// Path to the input `.ktx2` or `.dds` file.
+    #[arg(value_name = "input")]
+    input: PathBuf,
+
+    /// Output base name. Defaults to the input path without its extension.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension. Separate faces are written
+    /// as `{stem}-{face}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Mip level to extract.
+    #[arg(
+        value_name = "level",
+        long,
+        default_value_t = 0,
+        conflicts_with = "all_levels"
+    )]
+    level: u32,
+
+    /// Extract every mip level, writing level `i` to `{out_base}-mip{i}`.
+    #[arg(value_name = "all-levels", long)]
+    all_levels: bool,
+
+    /// How the output faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate output face files.
+    #[arg(value_name = "naming", long, value_enum, default_value_t)]
+    naming: FaceNaming,
+
+    /// Output image format. Defaults to `exr` for float texels and `png` otherwise.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
+}
+
+impl ContainerToFaces {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let out_base = self
+            .out_base
+            .unwrap_or_else(|| self.input.with_extension("").to_string_lossy().into_owned());
+        let bytes = deps.read_file(&self.input)?;
+        let (texel_format, mips) = CubeContainer::decode(&bytes)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            out_base,
+            self.format.unwrap_or(if texel_format.is_float() {
+                ImageFormat::Exr
+            } else {
+                ImageFormat::Png
+            }),
+        )?;
+
+        let levels: Vec<usize> = if self.all_levels {
+            (0..mips.len()).collect()
+        } else if (self.level as usize) < mips.len() {
+            vec![self.level as usize]
+        } else {
+            return Err(invalid_data(format!(
+                "{} has {} mip levels, so level {} does not exist",
+                self.input.display(),
+                mips.len(),
+                self.level
+            )));
+        };
+
+        for level in levels {
+            let level_output = if self.all_levels {
+                output.with_suffix(&format!("-mip{level}"))
+            } else {
+                output.clone()
+            };
+            let written = utilities::write_cube(
+                &deps,
+                &level_output,
+                self.layout,
+                self.naming,
+                false,
+                &mips[level],
+            )?;
+            deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,15 @@
-use crate::{Dependencies, Result};
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming, Sampling, Tonemap},
+};
 use clap::Parser;
-use std::path::Path;
+use tyt_common::{ImageFormat, ImageInput, ImageOutput};
 
 /// Converts an equirectangular panorama into six cube face images.
 #[derive(Clone, Debug, Parser)]
 pub struct EquirectToFaces {
-    /// Base name for the input equirectangular image (`{base}.png`).
+    /// Input equirectangular image path, or a base name tried with each supported extension
+    /// (`{base}.png`, `{base}.exr`, etc.).
     #[arg(value_name = "base")]
     base: String,
 
@@ -13,66 +17,73 @@ pub struct EquirectToFaces {
     #[arg(value_name = "out-base")]
     out_base: Option<String>,
 
+    /// Output path, with the image format taken from its extension. Separate faces are written
+    /// as `{stem}-{face}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
     /// Side length in pixels for each output face.
     #[arg(value_name = "size", short, long, default_value_t = 512)]
     size: u32,
-}
 
-/// Face crop positions in the c3x2 layout: `(col, row, face_name)`.
-const C3X2_FACES: &[(u32, u32, &str)] = &[
-    (0, 0, "left"),
-    (1, 0, "right"),
-    (2, 0, "up"),
-    (0, 1, "down"),
-    (1, 1, "front"),
-    (2, 1, "back"),
-];
+    /// How the output faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
 
-impl EquirectToFaces {
-    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
-        let out_base = self
-            .out_base
-            .unwrap_or_else(|| format!("{}-cube", self.base));
-        let tmp_dir = deps.create_temp_dir()?;
-        let result = equirect_to_faces(&deps, &self.base, &out_base, self.size, &tmp_dir);
-        deps.remove_dir_all(&tmp_dir)?;
-        result?;
-        deps.write_stdout(format!("Wrote: {out_base}-*.png\n").as_bytes())?;
-        Ok(())
-    }
-}
+    /// Naming scheme for separate output face files.
+    #[arg(value_name = "naming", long, value_enum, default_value_t)]
+    naming: FaceNaming,
 
-fn equirect_to_faces(
-    deps: &impl Dependencies,
-    base: &str,
-    out_base: &str,
-    size: u32,
-    tmp_dir: &Path,
-) -> Result<()> {
-    let c3x2_path = tmp_dir.join("c3x2.png");
-    let c3x2_str = c3x2_path.to_string_lossy().into_owned();
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
 
-    let vf = format!(
-        "v360=input=equirect:output=c3x2,scale={}:{}:flags=neighbor",
-        3 * size,
-        2 * size
-    );
-    deps.exec_ffmpeg(["-y", "-i", &format!("{base}.png"), "-vf", &vf, &c3x2_str])?;
+    /// Exposure adjustment in stops, applied when writing an LDR format.
+    #[arg(
+        value_name = "exposure",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    exposure: f32,
 
-    for &(col, row, face) in C3X2_FACES {
-        let crop = format!("crop={size}:{size}:{}:{}", col * size, row * size);
-        let out_path = format!("{out_base}-{face}.png");
-        deps.exec_ffmpeg([
-            "-y",
-            "-i",
-            &c3x2_str,
-            "-vf",
-            &crop,
-            "-frames:v",
-            "1",
-            &out_path,
-        ])?;
-    }
+    /// Tonemapping operator, applied when writing an LDR format.
+    #[arg(value_name = "tonemap", long, value_enum, default_value_t)]
+    tonemap: Tonemap,
+
+    /// Filter used to sample the panorama.
+    #[arg(value_name = "sampling", long, value_enum, default_value_t)]
+    sampling: Sampling,
+
+    /// Reproject with ffmpeg's `v360` filter instead of the native engine.
+    #[arg(value_name = "ffmpeg", long)]
+    ffmpeg: bool,
+}
 
-    Ok(())
+impl EquirectToFaces {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let input = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-cube", input.base)),
+            self.format.unwrap_or(input.format),
+        )?;
+        let mut faces = utilities::equirect_to_faces(
+            &deps,
+            &input.path,
+            self.size,
+            self.sampling,
+            self.ffmpeg,
+        )?;
+        if !output.format.is_hdr() {
+            for face in &mut faces {
+                self.tonemap.apply(face, self.exposure);
+            }
+        }
+        let written =
+            utilities::write_cube(&deps, &output, self.layout, self.naming, false, &faces)?;
+        deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        Ok(())
+    }
 }
@@ -1,11 +1,15 @@
-use crate::{Dependencies, Result, utilities};
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming, Sampling, Tonemap},
+};
 use clap::Parser;
-use std::path::Path;
+use tyt_common::{ImageFormat, ImageInput, ImageOutput};
 
 /// Converts an equirectangular panorama into a cube net cross layout.
 #[derive(Clone, Debug, Parser)]
 pub struct EquirectToNet {
-    /// Base name for the input equirectangular image (`{base}.png`).
+    /// Input equirectangular image path, or a base name tried with each supported extension
+    /// (`{base}.png`, `{base}.exr`, etc.).
     #[arg(value_name = "base")]
     base: String,
 
@@ -13,6 +17,11 @@ pub struct EquirectToNet {
     #[arg(value_name = "out-base")]
     out_base: Option<String>,
 
+    /// Output path, with the image format taken from its extension. Separate faces are written
+    /// as `{stem}-{face}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
     /// Side length in pixels for each cube face.
     #[arg(value_name = "size", short, long, default_value_t = 512)]
     size: u32,
@@ -20,129 +29,71 @@ pub struct EquirectToNet {
     /// Pad the output to a square canvas.
     #[arg(value_name = "square", long)]
     square: bool,
-}
-
-/// Face crop positions in the c3x2 layout used by the cube net: `(col, row, face_name)`.
-const C3X2_NET_FACES: &[(u32, u32, &str)] = &[
-    (0, 0, "left"),
-    (1, 0, "front"),
-    (2, 0, "right"),
-    (0, 1, "down"),
-    (1, 1, "back"),
-    (2, 1, "up"),
-];
 
-impl EquirectToNet {
-    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
-        let out_base = self
-            .out_base
-            .unwrap_or_else(|| format!("{}-cube-net", self.base));
-        let tmp_dir = deps.create_temp_dir()?;
-        let result = build_cube_net(
-            &deps,
-            &self.base,
-            &out_base,
-            self.size,
-            self.square,
-            &tmp_dir,
-        );
-        deps.remove_dir_all(&tmp_dir)?;
-        result?;
-        deps.write_stdout(format!("Wrote: {out_base}.png\n").as_bytes())?;
-        Ok(())
-    }
-}
+    /// How the faces are arranged in the output.
+    #[arg(value_name = "layout", long, value_enum, default_value_t = CubeLayout::Net)]
+    layout: CubeLayout,
 
-fn build_cube_net(
-    deps: &impl Dependencies,
-    base: &str,
-    out_base: &str,
-    size: u32,
-    do_square: bool,
-    tmp_dir: &Path,
-) -> Result<()> {
-    let c3x2_path = tmp_dir.join("c3x2.png");
-    let c3x2_str = c3x2_path.to_string_lossy().into_owned();
+    /// Naming scheme for separate output face files (with `--layout faces`).
+    #[arg(value_name = "naming", long, value_enum, default_value_t)]
+    naming: FaceNaming,
 
-    let vf = format!(
-        "v360=input=equirect:output=c3x2,scale={}:{}:flags=neighbor",
-        3 * size,
-        2 * size
-    );
-    deps.exec_ffmpeg(["-y", "-i", &format!("{base}.png"), "-vf", &vf, &c3x2_str])?;
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
 
-    for &(col, row, face) in C3X2_NET_FACES {
-        let crop = format!("crop={size}:{size}:{}:{}", col * size, row * size);
-        let out_path = tmp_dir.join(format!("{face}.png"));
-        let out_str = out_path.to_string_lossy().into_owned();
-        deps.exec_ffmpeg([
-            "-y",
-            "-i",
-            &c3x2_str,
-            "-vf",
-            &crop,
-            "-frames:v",
-            "1",
-            &out_str,
-        ])?;
-    }
+    /// Exposure adjustment in stops, applied when writing an LDR format.
+    #[arg(
+        value_name = "exposure",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    exposure: f32,
 
-    let cube_net_path = tmp_dir.join("cube-net.png");
-    let cube_net_str = cube_net_path.to_string_lossy().into_owned();
+    /// Tonemapping operator, applied when writing an LDR format.
+    #[arg(value_name = "tonemap", long, value_enum, default_value_t)]
+    tonemap: Tonemap,
 
-    let canvas = format!("{}x{}", 4 * size, 3 * size);
-    let right_path = tmp_dir.join("right.png").to_string_lossy().into_owned();
-    let up_path = tmp_dir.join("up.png").to_string_lossy().into_owned();
-    let front_path = tmp_dir.join("front.png").to_string_lossy().into_owned();
-    let back_path = tmp_dir.join("back.png").to_string_lossy().into_owned();
-    let left_path = tmp_dir.join("left.png").to_string_lossy().into_owned();
-    let down_path = tmp_dir.join("down.png").to_string_lossy().into_owned();
+    /// Filter used to sample the panorama.
+    #[arg(value_name = "sampling", long, value_enum, default_value_t)]
+    sampling: Sampling,
 
-    deps.exec_magick([
-        "-size",
-        &canvas,
-        "xc:transparent",
-        "(",
-        &right_path,
-        "-rotate",
-        "270",
-        ")",
-        "-geometry",
-        &format!("+{}+0", size),
-        "-composite",
-        &up_path,
-        "-geometry",
-        &format!("+0+{}", size),
-        "-composite",
-        &front_path,
-        "-geometry",
-        &format!("+{}+{}", size, size),
-        "-composite",
-        &back_path,
-        "-geometry",
-        &format!("+{}+{}", 2 * size, size),
-        "-composite",
-        &left_path,
-        "-geometry",
-        &format!("+{}+{}", 3 * size, size),
-        "-composite",
-        "(",
-        &down_path,
-        "-rotate",
-        "90",
-        ")",
-        "-geometry",
-        &format!("+{}+{}", size, 2 * size),
-        "-composite",
-        &cube_net_str,
-    ])?;
+    /// Reproject with ffmpeg's `v360` filter instead of the native engine.
+    #[arg(value_name = "ffmpeg", long)]
+    ffmpeg: bool,
+}
 
-    let out_path = format!("{out_base}.png");
-    if do_square {
-        utilities::square(deps, &cube_net_str, &out_path)?;
-    } else {
-        deps.rename_file(&cube_net_path, &out_path)?;
+impl EquirectToNet {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let input = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-cube-net", input.base)),
+            self.format.unwrap_or(input.format),
+        )?;
+        let mut faces = utilities::equirect_to_faces(
+            &deps,
+            &input.path,
+            self.size,
+            self.sampling,
+            self.ffmpeg,
+        )?;
+        if !output.format.is_hdr() {
+            for face in &mut faces {
+                self.tonemap.apply(face, self.exposure);
+            }
+        }
+        let written = utilities::write_cube(
+            &deps,
+            &output,
+            self.layout,
+            self.naming,
+            self.square,
+            &faces,
+        )?;
+        deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        Ok(())
     }
-
-    Ok(())
 }
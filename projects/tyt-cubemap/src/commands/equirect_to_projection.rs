@@ -0,0 +1,99 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, Projection, Sampling, Tonemap},
+};
+use clap::{Parser, ValueEnum};
+use tyt_common::{ImageFormat, ImageInput, ImageOutput};
+
+/// Converts an equirectangular panorama into a little planet, fisheye, mirror ball, angular
+/// light probe, octahedral map, or equi-angular cubemap.
+#[derive(Clone, Debug, Parser)]
+pub struct EquirectToProjection {
+    /// Input equirectangular image path, or a base name tried with each supported extension
+    /// (`{base}.png`, `{base}.exr`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-{projection}`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Projection to convert to.
+    #[arg(value_name = "projection", long, value_enum)]
+    projection: Projection,
+
+    /// Output height in pixels. The width follows the projection's aspect ratio.
+    #[arg(value_name = "size", short, long, default_value_t = 512)]
+    size: u32,
+
+    /// Field of view in degrees for `little-planet` (default 270) and `fisheye` (default 180).
+    #[arg(value_name = "fov", long)]
+    fov: Option<f64>,
+
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
+
+    /// Exposure adjustment in stops, applied when writing an LDR format.
+    #[arg(
+        value_name = "exposure",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    exposure: f32,
+
+    /// Tonemapping operator, applied when writing an LDR format.
+    #[arg(value_name = "tonemap", long, value_enum, default_value_t)]
+    tonemap: Tonemap,
+
+    /// Filter used to sample the panorama.
+    #[arg(value_name = "sampling", long, value_enum, default_value_t)]
+    sampling: Sampling,
+}
+
+impl EquirectToProjection {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let input = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_base = self.out_base.unwrap_or_else(|| {
+            let projection = self
+                .projection
+                .to_possible_value()
+                .expect("no skipped variants");
+            format!("{}-{}", input.base, projection.get_name())
+        });
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            out_base,
+            self.format.unwrap_or(input.format),
+        )?;
+        let equirect = utilities::load_image(&deps, &input.path)?;
+
+        let fov = self
+            .fov
+            .or(self.projection.default_fov())
+            .unwrap_or_default()
+            .to_radians();
+        let width = (self.size as f64 * self.projection.aspect()).round() as u32;
+        let mut image = utilities::equirect_to_projection_image(
+            &equirect,
+            self.projection,
+            fov,
+            width,
+            self.size,
+            self.sampling,
+        );
+
+        if !output.format.is_hdr() {
+            self.tonemap.apply(&mut image, self.exposure);
+        }
+        let out_path = output.path();
+        utilities::write_image(&deps, &out_path, &image)?;
+        deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        Ok(())
+    }
+}
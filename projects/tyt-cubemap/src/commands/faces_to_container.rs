@@ -0,0 +1,93 @@
+use crate::{
+    Dependencies, Error, Result,
+    utilities::{self, CubeContainer, CubeLayout, FaceNaming, TexelFormat},
+};
+use clap::Parser;
+use tyt_common::{invalid_data, split_extension};
+
+/// Packs cube faces into a single KTX2 or DDS cubemap file.
+///
+/// Faces are stored in the `+X`, `-X`, `+Y`, `-Y`, `+Z`, `-Z` order and orientation that
+/// OpenGL, Vulkan, and Direct3D sample cube maps with.
+#[derive(Clone, Debug, Parser)]
+pub struct FacesToContainer {
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts.
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path. A `.ktx2` or `.dds` extension chooses the container.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "container"])]
+    output: Option<String>,
+
+    /// Container file format.
+    #[arg(value_name = "container", long, value_enum, default_value_t)]
+    container: CubeContainer,
+
+    /// Texel format. Defaults to `rgba16f` for HDR input and `rgba8` otherwise.
+    #[arg(value_name = "texel-format", long, value_enum)]
+    texel_format: Option<TexelFormat>,
+
+    /// Include a box-filtered mip chain down to 1x1. Requires a power-of-two face size.
+    #[arg(value_name = "mips", long)]
+    mips: bool,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+}
+
+impl FacesToContainer {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+        let (out_path, container) = match self.output {
+            Some(output) => {
+                let container = split_extension(&output)
+                    .and_then(|(_, ext)| CubeContainer::from_extension(ext))
+                    .ok_or_else(|| {
+                        invalid_data::<Error>(format!(
+                            "`{output}` does not end in `.ktx2` or `.dds`"
+                        ))
+                    })?;
+                (output, container)
+            }
+            None => {
+                let out_base = self.out_base.unwrap_or_else(|| cube.base.clone());
+                (
+                    format!("{out_base}.{}", self.container.extension()),
+                    self.container,
+                )
+            }
+        };
+        let faces = cube.faces;
+        let texel_format = self.texel_format.unwrap_or(if cube.format.is_hdr() {
+            TexelFormat::Rgba16f
+        } else {
+            TexelFormat::Rgba8
+        });
+
+        let mips = if self.mips {
+            if !faces[0].width.is_power_of_two() {
+                return Err(invalid_data(format!(
+                    "mip chains require a power-of-two face size, but faces are {}x{}",
+                    faces[0].width, faces[0].height
+                )));
+            }
+            utilities::face_mip_chain(&faces)
+        } else {
+            vec![faces]
+        };
+
+        deps.write_file(&out_path, &container.encode(&mips, texel_format))?;
+        deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        Ok(())
+    }
+}
@@ -1,27 +1,76 @@
-use crate::{Dependencies, Result, utilities};
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming, Sampling, Tonemap},
+};
 use clap::Parser;
+use tyt_common::{ImageFormat, ImageOutput};
 
 /// Converts six cube face images into a single equirectangular panorama.
 #[derive(Clone, Debug, Parser)]
 pub struct FacesToEquirect {
-    /// Base name for input face files (`{base}-left.png`, etc.).
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts.
     #[arg(value_name = "base")]
     base: String,
 
     /// Output base name. Defaults to `{base}-equirect`.
     #[arg(value_name = "out-base")]
     out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
+
+    /// Exposure adjustment in stops, applied when writing an LDR format.
+    #[arg(
+        value_name = "exposure",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    exposure: f32,
+
+    /// Tonemapping operator, applied when writing an LDR format.
+    #[arg(value_name = "tonemap", long, value_enum, default_value_t)]
+    tonemap: Tonemap,
+
+    /// Filter used to sample the cube faces.
+    #[arg(value_name = "sampling", long, value_enum, default_value_t)]
+    sampling: Sampling,
+
+    /// Reproject with ffmpeg's `v360` filter instead of the native engine.
+    #[arg(value_name = "ffmpeg", long)]
+    ffmpeg: bool,
 }
 
 impl FacesToEquirect {
     pub fn execute(self, deps: impl Dependencies) -> Result<()> {
-        let out_base = self
-            .out_base
-            .unwrap_or_else(|| format!("{}-equirect", self.base));
-        let tmp_dir = deps.create_temp_dir()?;
-        let result = utilities::faces_to_equirect(&deps, &self.base, &out_base, &tmp_dir);
-        deps.remove_dir_all(&tmp_dir)?;
-        let out_path = result?;
+        let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-equirect", cube.base)),
+            self.format.unwrap_or(cube.format),
+        )?;
+        let mut equirect =
+            utilities::faces_to_equirect(&deps, &cube.faces, self.sampling, self.ffmpeg)?;
+        if !output.format.is_hdr() {
+            self.tonemap.apply(&mut equirect, self.exposure);
+        }
+        let out_path = output.path();
+        utilities::write_image(&deps, &out_path, &equirect)?;
         deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
         Ok(())
     }
@@ -0,0 +1,61 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming},
+};
+use clap::Parser;
+use tyt_common::{ImageFormat, ImageOutput};
+
+/// Assembles six cube face images into a cube net cross layout.
+#[derive(Clone, Debug, Parser)]
+pub struct FacesToNet {
+    /// Input face files, as their base name (`{base}-left.png`, etc.) or the path of any one face.
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-net`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension. Separate faces are written
+    /// as `{stem}-{face}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Naming scheme for the input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+
+    /// How the faces are arranged in the output image.
+    #[arg(value_name = "layout", long, value_enum, default_value_t = CubeLayout::Net)]
+    layout: CubeLayout,
+
+    /// Pad the output to a square canvas.
+    #[arg(value_name = "square", long)]
+    square: bool,
+
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
+}
+
+impl FacesToNet {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let cube = utilities::load_cube(&deps, &self.base, CubeLayout::Faces, self.naming)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-net", cube.base)),
+            self.format.unwrap_or(cube.format),
+        )?;
+        let written = utilities::write_cube(
+            &deps,
+            &output,
+            self.layout,
+            cube.naming,
+            self.square,
+            &cube.faces,
+        )?;
+        deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        Ok(())
+    }
+}
@@ -1,10 +1,16 @@
-use crate::{Dependencies, Result, utilities};
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming, Sampling},
+};
 use clap::Parser;
+use tyt_common::{Dither, ImageOutput, Palette};
 
-/// Pixelates cube face images and then converts them to an equirectangular panorama.
+/// Pixelates cube face images, optionally reducing them to a palette, and then converts them to
+/// an equirectangular panorama.
 #[derive(Clone, Debug, Parser)]
 pub struct FacesToPixelatedEquirect {
-    /// Base name for input face files (`{base}-left.png`, etc.).
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts.
     #[arg(value_name = "base")]
     base: String,
 
@@ -12,29 +18,77 @@ pub struct FacesToPixelatedEquirect {
     #[arg(value_name = "out-base")]
     out_base: Option<String>,
 
+    /// Output path, with the image format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base"])]
+    output: Option<String>,
+
     /// Target height in pixels for pixelation (halved internally).
     #[arg(value_name = "size", short, long, default_value_t = 256)]
     size: u32,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+
+    /// Palette to reduce the pixelated faces to: a `.hex` file of one hex color per line, a GIMP
+    /// `.gpl` palette, or an image whose distinct colors form the palette.
+    #[arg(value_name = "palette", long)]
+    palette: Option<String>,
+
+    /// How to dither when reducing to `--palette`.
+    #[arg(
+        value_name = "dither",
+        long,
+        value_enum,
+        default_value_t,
+        requires = "palette"
+    )]
+    dither: Dither,
+
+    /// Filter used to sample the pixelated faces. Defaults to `nearest` with `--palette`, so the
+    /// panorama keeps to the palette's colors, and to `bilinear` otherwise.
+    #[arg(value_name = "sampling", long, value_enum)]
+    sampling: Option<Sampling>,
+
+    /// Reproject with ffmpeg's `v360` filter instead of the native engine.
+    #[arg(value_name = "ffmpeg", long)]
+    ffmpeg: bool,
 }
 
 impl FacesToPixelatedEquirect {
     pub fn execute(self, deps: impl Dependencies) -> Result<()> {
-        let out_base = self
-            .out_base
-            .unwrap_or_else(|| format!("{}-px-equirect", self.base));
         let half_size = self.size / 2;
 
-        let tmp_dir = deps.create_temp_dir()?;
-        let tmp_base = tmp_dir.join("face");
-        let tmp_base_str = tmp_base.to_string_lossy().into_owned();
+        let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-px-equirect", cube.base)),
+            cube.format,
+        )?;
 
-        let result = (|| {
-            utilities::pixelate_faces(&deps, &self.base, &tmp_base_str, half_size)?;
-            utilities::faces_to_equirect(&deps, &tmp_base_str, &out_base, &tmp_dir)
-        })();
+        let mut pixelated = utilities::pixelate_faces(&cube.faces, half_size);
+        if let Some(palette) = &self.palette {
+            let palette = Palette::load(
+                palette,
+                |path| deps.read_file(path),
+                |path| Ok(utilities::load_image(&deps, path)?.to_srgb8()),
+            )?;
+            utilities::quantize_faces(&mut pixelated, &palette, self.dither);
+        }
 
-        deps.remove_dir_all(&tmp_dir)?;
-        let out_path = result?;
+        let sampling = self.sampling.unwrap_or(if self.palette.is_some() {
+            Sampling::Nearest
+        } else {
+            Sampling::Bilinear
+        });
+        let equirect = utilities::faces_to_equirect(&deps, &pixelated, sampling, self.ffmpeg)?;
+        let out_path = output.path();
+        utilities::write_image(&deps, &out_path, &equirect)?;
         deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
         Ok(())
     }
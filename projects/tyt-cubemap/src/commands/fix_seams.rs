@@ -0,0 +1,76 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming, SeamFix},
+};
+use clap::Parser;
+use tyt_common::ImageOutput;
+
+/// Makes the texels along each of the 12 cube edges agree with the neighboring face.
+#[derive(Clone, Debug, Parser)]
+pub struct FixSeams {
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts.
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-seamless`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension. Separate faces are written
+    /// as `{stem}-{face}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
+    /// How border texels are made to agree.
+    #[arg(value_name = "mode", long, value_enum, default_value_t)]
+    mode: SeamFix,
+
+    /// Number of texels into each face over which the correction fades out. Ignored by
+    /// `--mode copy`, which only rewrites the border texels.
+    #[arg(value_name = "width", long, default_value_t = 1)]
+    width: u32,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+
+    /// How the output faces are arranged. Defaults to the input layout.
+    #[arg(value_name = "out-layout", long, value_enum)]
+    out_layout: Option<CubeLayout>,
+
+    /// Naming scheme for separate output face files. Defaults to the input naming scheme.
+    #[arg(value_name = "out-naming", long, value_enum)]
+    out_naming: Option<FaceNaming>,
+}
+
+impl FixSeams {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-seamless", cube.base)),
+            cube.format,
+        )?;
+        let mut faces = cube.faces;
+
+        let seams = utilities::cube_seams(faces[0].width);
+        utilities::fix_seams(&mut faces, &seams, self.mode, self.width);
+
+        let written = utilities::write_cube(
+            &deps,
+            &output,
+            self.out_layout.unwrap_or(self.layout),
+            self.out_naming.unwrap_or(cube.naming),
+            false,
+            &faces,
+        )?;
+        deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming, Tonemap},
+};
+use clap::Parser;
+use tyt_common::{ImageFormat, ImageOutput, split_extension};
+
+/// Synthesizes a sky from a JSON description as a cubemap or equirectangular panorama.
+///
+/// The description holds a `gradient` of `{ "elevation", "color" }` stops and optional `sun`,
+/// `stars`, and `clouds` objects. Colors are linear RGB and angles are in degrees, with azimuth
+/// turning right from the front. Stars and clouds are seeded, so the same description always
+/// produces the same sky.
+#[derive(Clone, Debug, Parser)]
+pub struct Generate {
+    /// Sky description JSON path.
+    #[arg(value_name = "description")]
+    description: String,
+
+    /// Output base name. Defaults to the description path without its extension.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension. Separate faces are written
+    /// as `{stem}-{face}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Write an equirectangular image instead of a cubemap.
+    #[arg(value_name = "equirect", long)]
+    equirect: bool,
+
+    /// Side length in pixels for each face, or the height of the equirectangular image, which is
+    /// twice as wide.
+    #[arg(value_name = "size", short, long, default_value_t = 512)]
+    size: u32,
+
+    /// How the output faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate output face files.
+    #[arg(value_name = "naming", long, value_enum, default_value_t)]
+    naming: FaceNaming,
+
+    /// Output image format.
+    #[arg(value_name = "format", long, value_enum, default_value_t)]
+    format: ImageFormat,
+
+    /// Exposure adjustment in stops, applied when writing an LDR format.
+    #[arg(
+        value_name = "exposure",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    exposure: f32,
+
+    /// Tonemapping operator, applied when writing an LDR format.
+    #[arg(value_name = "tonemap", long, value_enum, default_value_t)]
+    tonemap: Tonemap,
+}
+
+impl Generate {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let description = deps.parse_sky_description_json(&deps.read_file(&self.description)?)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base.unwrap_or_else(|| {
+                split_extension(&self.description)
+                    .map_or(self.description.clone(), |(base, _)| base.to_owned())
+            }),
+            self.format,
+        )?;
+        let size = self.size.max(1);
+
+        let written = if self.equirect {
+            let mut image = utilities::generate_equirect(&description, 2 * size, size);
+            if !output.format.is_hdr() {
+                self.tonemap.apply(&mut image, self.exposure);
+            }
+            let out_path = output.path();
+            utilities::write_image(&deps, &out_path, &image)?;
+            out_path
+        } else {
+            let mut faces = utilities::generate_faces(&description, size);
+            if !output.format.is_hdr() {
+                for face in &mut faces {
+                    self.tonemap.apply(face, self.exposure);
+                }
+            }
+            utilities::write_cube(&deps, &output, self.layout, self.naming, false, &faces)?
+        };
+        deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        Ok(())
+    }
+}
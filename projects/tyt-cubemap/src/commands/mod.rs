@@ -1,11 +1,45 @@
+mod analyze;
+mod batch;
+mod check_seams;
+mod container_to_faces;
 mod equirect_to_faces;
 mod equirect_to_net;
+mod equirect_to_projection;
+mod faces_to_container;
 mod faces_to_equirect;
+mod faces_to_net;
 mod faces_to_pixelated_equirect;
+mod fix_seams;
+mod generate;
+mod net_to_faces;
 mod pixelate_faces;
+mod prefilter;
+mod preview;
+mod projection_to_equirect;
+mod rotate;
+mod sh;
+mod square_image;
+mod video_to_frames;
 
+pub use analyze::*;
+pub use batch::*;
+pub use check_seams::*;
+pub use container_to_faces::*;
 pub use equirect_to_faces::*;
 pub use equirect_to_net::*;
+pub use equirect_to_projection::*;
+pub use faces_to_container::*;
 pub use faces_to_equirect::*;
+pub use faces_to_net::*;
 pub use faces_to_pixelated_equirect::*;
+pub use fix_seams::*;
+pub use generate::*;
+pub use net_to_faces::*;
 pub use pixelate_faces::*;
+pub use prefilter::*;
+pub use preview::*;
+pub use projection_to_equirect::*;
+pub use rotate::*;
+pub use sh::*;
+pub use square_image::*;
+pub use video_to_frames::*;
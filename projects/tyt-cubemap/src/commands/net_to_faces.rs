@@ -0,0 +1,58 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming},
+};
+use clap::Parser;
+use tyt_common::{ImageFormat, ImageOutput};
+
+/// Slices a cube net cross into six cube face images, undoing the rotations applied to the net.
+#[derive(Clone, Debug, Parser)]
+pub struct NetToFaces {
+    /// Input net image path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.exr`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-faces`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension. Faces are written as
+    /// `{stem}-{face}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// How the faces are arranged in the input image.
+    #[arg(value_name = "layout", long, value_enum, default_value_t = CubeLayout::Net)]
+    layout: CubeLayout,
+
+    /// Naming scheme for the output face files.
+    #[arg(value_name = "naming", long, value_enum, default_value_t)]
+    naming: FaceNaming,
+
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
+}
+
+impl NetToFaces {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let cube = utilities::load_cube(&deps, &self.base, self.layout, None)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-faces", cube.base)),
+            self.format.unwrap_or(cube.format),
+        )?;
+        let written = utilities::write_cube(
+            &deps,
+            &output,
+            CubeLayout::Faces,
+            self.naming,
+            false,
+            &cube.faces,
+        )?;
+        deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
-use crate::{Dependencies, Result, utilities};
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming},
+};
 use clap::Parser;
+use tyt_common::{Dither, ImageOutput, Palette};
 
-/// Pixelates (point-resizes) six cube face images.
+/// Pixelates (point-resizes) six cube face images, optionally reducing them to a palette.
 #[derive(Clone, Debug, Parser)]
 pub struct PixelateFaces {
-    /// Base name for input face files (`{base}-left.png`, etc.).
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts.
     #[arg(value_name = "base")]
     base: String,
 
@@ -12,16 +17,75 @@ pub struct PixelateFaces {
     #[arg(value_name = "out-base")]
     out_base: Option<String>,
 
+    /// Output path, with the image format taken from its extension. Separate faces are written
+    /// as `{stem}-{face}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
     /// Target height in pixels for each face.
     #[arg(value_name = "size", short, long, default_value_t = 256)]
     size: u32,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+
+    /// How the output faces are arranged. Defaults to the input layout.
+    #[arg(value_name = "out-layout", long, value_enum)]
+    out_layout: Option<CubeLayout>,
+
+    /// Naming scheme for separate output face files. Defaults to the input naming scheme.
+    #[arg(value_name = "out-naming", long, value_enum)]
+    out_naming: Option<FaceNaming>,
+
+    /// Palette to reduce the pixelated faces to: a `.hex` file of one hex color per line, a GIMP
+    /// `.gpl` palette, or an image whose distinct colors form the palette.
+    #[arg(value_name = "palette", long)]
+    palette: Option<String>,
+
+    /// How to dither when reducing to `--palette`.
+    #[arg(
+        value_name = "dither",
+        long,
+        value_enum,
+        default_value_t,
+        requires = "palette"
+    )]
+    dither: Dither,
 }
 
 impl PixelateFaces {
     pub fn execute(self, deps: impl Dependencies) -> Result<()> {
-        let out_base = self.out_base.unwrap_or_else(|| format!("{}-px", self.base));
-        utilities::pixelate_faces(&deps, &self.base, &out_base, self.size)?;
-        deps.write_stdout(format!("Wrote resized faces: {out_base}-*.png\n").as_bytes())?;
+        let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base.unwrap_or_else(|| format!("{}-px", cube.base)),
+            cube.format,
+        )?;
+
+        let mut pixelated = utilities::pixelate_faces(&cube.faces, self.size);
+        if let Some(palette) = &self.palette {
+            let palette = Palette::load(
+                palette,
+                |path| deps.read_file(path),
+                |path| Ok(utilities::load_image(&deps, path)?.to_srgb8()),
+            )?;
+            utilities::quantize_faces(&mut pixelated, &palette, self.dither);
+        }
+
+        let written = utilities::write_cube(
+            &deps,
+            &output,
+            self.out_layout.unwrap_or(self.layout),
+            self.out_naming.unwrap_or(cube.naming),
+            false,
+            &pixelated,
+        )?;
+        deps.write_stdout(format!("Wrote resized faces: {written}\n").as_bytes())?;
         Ok(())
     }
 }
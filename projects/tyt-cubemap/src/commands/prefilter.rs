@@ -0,0 +1,109 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming},
+};
+use clap::Parser;
+use tyt_common::{ImageFormat, ImageOutput};
+
+/// Generates a GGX-prefiltered specular mip chain from cube faces.
+///
+/// Mip `i` of `n` is convolved with perceptual roughness `i / (n - 1)` and written as a face set
+/// to `{out_base}-mip{i}`, or beside `--output` as `{stem}-mip{i}.{ext}`.
+#[derive(Clone, Debug, Parser)]
+pub struct Prefilter {
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts.
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-ggx`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension. Each mip is written beside
+    /// it with a `-mip{i}` suffix.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Side length in pixels for each face of mip 0. Defaults to the input face size.
+    #[arg(value_name = "size", short, long)]
+    size: Option<u32>,
+
+    /// Number of mip levels. Defaults to a full chain down to 1x1.
+    #[arg(value_name = "levels", long)]
+    levels: Option<u32>,
+
+    /// Number of GGX samples per output texel.
+    #[arg(
+        value_name = "samples",
+        long,
+        default_value_t = 128,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    samples: u32,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+
+    /// How the output faces of each mip are arranged.
+    #[arg(value_name = "out-layout", long, value_enum, default_value_t)]
+    out_layout: CubeLayout,
+
+    /// Naming scheme for separate output face files. Defaults to `words`.
+    #[arg(value_name = "out-naming", long, value_enum, default_value_t)]
+    out_naming: FaceNaming,
+
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
+}
+
+impl Prefilter {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-ggx", cube.base)),
+            self.format.unwrap_or(cube.format),
+        )?;
+        let faces = cube.faces;
+
+        let size = self.size.unwrap_or(faces[0].width).max(1);
+        let levels = self
+            .levels
+            .unwrap_or(u32::BITS - size.leading_zeros())
+            .clamp(1, u32::BITS - size.leading_zeros());
+        let source_mips = utilities::face_mip_chain(&faces);
+
+        for level in 0..levels {
+            let roughness = if levels > 1 {
+                level as f64 / (levels - 1) as f64
+            } else {
+                0.0
+            };
+            let mip = utilities::prefilter_ggx_faces(
+                &source_mips,
+                (size >> level).max(1),
+                roughness,
+                self.samples,
+            );
+            let written = utilities::write_cube(
+                &deps,
+                &output.with_suffix(&format!("-mip{level}")),
+                self.out_layout,
+                self.out_naming,
+                false,
+                &mip,
+            )?;
+            deps.write_stdout(format!("Wrote: {written} (roughness {roughness:.3})\n").as_bytes())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming, Image, Sampling, Tonemap},
+};
+use clap::Parser;
+use ty_math::TyVector3;
+use tyt_common::{ImageFormat, ImageInput, ImageOutput};
+
+/// Renders a pinhole camera view of a cubemap or equirectangular panorama.
+///
+/// With `--turntable N`, writes `N` frames evenly spaced around the horizon to
+/// `{out_base}-{i}`, starting at `--yaw`. With `--output`, frames are named `{stem}-{i}.{ext}`.
+#[derive(Clone, Debug, Parser)]
+pub struct Preview {
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts and `--equirect`.
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-preview`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Read the input as an equirectangular image instead of a cubemap.
+    #[arg(value_name = "equirect", long)]
+    equirect: bool,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+
+    /// Degrees to turn the camera right from the front.
+    #[arg(
+        value_name = "yaw",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    yaw: f64,
+
+    /// Degrees to tilt the camera up from the horizon.
+    #[arg(
+        value_name = "pitch",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    pitch: f64,
+
+    /// Horizontal field of view in degrees, between 0 and 180.
+    #[arg(value_name = "fov", long, default_value_t = 90.0, value_parser = parse_fov)]
+    fov: f64,
+
+    /// Output width in pixels.
+    #[arg(value_name = "width", long, default_value_t = 640)]
+    width: u32,
+
+    /// Output height in pixels.
+    #[arg(value_name = "height", long, default_value_t = 360)]
+    height: u32,
+
+    /// Render this many frames around the horizon instead of a single view.
+    #[arg(
+        value_name = "turntable",
+        long,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    turntable: Option<u32>,
+
+    /// Also combine the turntable frames into a grid at `{out_base}-sheet`.
+    #[arg(value_name = "contact-sheet", long, requires = "turntable")]
+    contact_sheet: bool,
+
+    /// Output image format.
+    #[arg(value_name = "format", long, value_enum, default_value_t)]
+    format: ImageFormat,
+
+    /// Exposure adjustment in stops, applied when writing an LDR format.
+    #[arg(
+        value_name = "exposure",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    exposure: f32,
+
+    /// Tonemapping operator, applied when writing an LDR format.
+    #[arg(value_name = "tonemap", long, value_enum, default_value_t)]
+    tonemap: Tonemap,
+
+    /// Filter used to sample the input.
+    #[arg(value_name = "sampling", long, value_enum, default_value_t)]
+    sampling: Sampling,
+}
+
+impl Preview {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let mut frames = Vec::new();
+        let yaws: Vec<f64> = match self.turntable {
+            Some(count) => (0..count)
+                .map(|i| self.yaw + 360.0 * i as f64 / count as f64)
+                .collect(),
+            None => vec![self.yaw],
+        };
+        let in_base = if self.equirect {
+            let input = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+            let equirect = utilities::load_image(&deps, &input.path)?;
+            for &yaw in &yaws {
+                frames.push(self.render(yaw, |dir| {
+                    utilities::sample_equirect(&equirect, dir, self.sampling)
+                }));
+            }
+            input.base
+        } else {
+            let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+            for &yaw in &yaws {
+                frames.push(self.render(yaw, |dir| {
+                    utilities::sample_faces(&cube.faces, dir, self.sampling)
+                }));
+            }
+            cube.base
+        };
+
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .clone()
+                .unwrap_or_else(|| format!("{in_base}-preview")),
+            self.format,
+        )?;
+        if !output.format.is_hdr() {
+            for frame in &mut frames {
+                self.tonemap.apply(frame, self.exposure);
+            }
+        }
+        if self.turntable.is_none() {
+            let out_path = output.path();
+            utilities::write_image(&deps, &out_path, &frames[0])?;
+            deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+            return Ok(());
+        }
+
+        let digits = (frames.len() - 1).to_string().len();
+        for (i, frame) in frames.iter().enumerate() {
+            utilities::write_image(
+                &deps,
+                output.with_suffix(&format!("-{i:0digits$}")).path(),
+                frame,
+            )?;
+        }
+        deps.write_stdout(format!("Wrote: {}-*.{}\n", output.base, output.extension).as_bytes())?;
+        if self.contact_sheet {
+            let out_path = output.with_suffix("-sheet").path();
+            utilities::write_image(&deps, &out_path, &utilities::contact_sheet(&frames))?;
+            deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn render(&self, yaw: f64, sample: impl Fn(TyVector3) -> [f32; 4]) -> Image {
+        utilities::render_perspective(sample, yaw, self.pitch, self.fov, self.width, self.height)
+    }
+}
+
+fn parse_fov(value: &str) -> std::result::Result<f64, String> {
+    let fov: f64 = value.parse().map_err(|e| format!("{e}"))?;
+    if !(fov > 0.0 && fov < 180.0) {
+        return Err(format!("{fov} is not between 0 and 180 degrees"));
+    }
+    Ok(fov)
+}
@@ -0,0 +1,95 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, Projection, Sampling, Tonemap},
+};
+use clap::Parser;
+use tyt_common::{ImageFormat, ImageInput, ImageOutput};
+
+/// Converts a little planet, fisheye, mirror ball, angular light probe, octahedral map, or
+/// equi-angular cubemap back into an equirectangular panorama.
+///
+/// Directions the input does not cover are left transparent.
+#[derive(Clone, Debug, Parser)]
+pub struct ProjectionToEquirect {
+    /// Input image path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.exr`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-equirect`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Projection of the input image.
+    #[arg(value_name = "projection", long, value_enum)]
+    projection: Projection,
+
+    /// Output height in pixels; the width is twice this. Defaults to the input height.
+    #[arg(value_name = "size", short, long)]
+    size: Option<u32>,
+
+    /// Field of view in degrees for `little-planet` (default 270) and `fisheye` (default 180).
+    #[arg(value_name = "fov", long)]
+    fov: Option<f64>,
+
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
+
+    /// Exposure adjustment in stops, applied when writing an LDR format.
+    #[arg(
+        value_name = "exposure",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    exposure: f32,
+
+    /// Tonemapping operator, applied when writing an LDR format.
+    #[arg(value_name = "tonemap", long, value_enum, default_value_t)]
+    tonemap: Tonemap,
+
+    /// Filter used to sample the input image.
+    #[arg(value_name = "sampling", long, value_enum, default_value_t)]
+    sampling: Sampling,
+}
+
+impl ProjectionToEquirect {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let input = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-equirect", input.base)),
+            self.format.unwrap_or(input.format),
+        )?;
+        let image = utilities::load_image(&deps, &input.path)?;
+
+        let fov = self
+            .fov
+            .or(self.projection.default_fov())
+            .unwrap_or_default()
+            .to_radians();
+        let height = self.size.unwrap_or(image.height);
+        let mut equirect = utilities::projection_to_equirect_image(
+            &image,
+            self.projection,
+            fov,
+            height * 2,
+            height,
+            self.sampling,
+        );
+
+        if !output.format.is_hdr() {
+            self.tonemap.apply(&mut equirect, self.exposure);
+        }
+        let out_path = output.path();
+        utilities::write_image(&deps, &out_path, &equirect)?;
+        deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CoordinateSystem, CubeLayout, DirectionTransform, FaceNaming, Sampling},
+};
+use clap::Parser;
+use tyt_common::{ImageFormat, ImageInput, ImageOutput};
+
+/// Rotates a cubemap or equirectangular panorama, optionally converting it between engine
+/// coordinate systems.
+///
+/// The yaw, pitch, and roll are applied first, in the input's frame. Face sets are resampled
+/// losslessly when every rotation is a multiple of 90 degrees.
+#[derive(Clone, Debug, Parser)]
+pub struct Rotate {
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts and `--equirect`.
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-rotated`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension. Separate faces are written
+    /// as `{stem}-{face}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Read the input as an equirectangular image instead of a cubemap.
+    #[arg(value_name = "equirect", long)]
+    equirect: bool,
+
+    /// Degrees to turn content around the up axis. Positive values move it right.
+    #[arg(
+        value_name = "yaw",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    yaw: f64,
+
+    /// Degrees to tilt content. Positive values move the front up.
+    #[arg(
+        value_name = "pitch",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    pitch: f64,
+
+    /// Degrees to turn content around the forward axis. Positive values turn it clockwise.
+    #[arg(
+        value_name = "roll",
+        long,
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    roll: f64,
+
+    /// Coordinate system the input is laid out for.
+    #[arg(value_name = "from", long, value_enum, requires = "to")]
+    from: Option<CoordinateSystem>,
+
+    /// Coordinate system to lay the output out for.
+    #[arg(value_name = "to", long, value_enum, requires = "from")]
+    to: Option<CoordinateSystem>,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+
+    /// How the output faces are arranged. Defaults to the input layout.
+    #[arg(value_name = "out-layout", long, value_enum)]
+    out_layout: Option<CubeLayout>,
+
+    /// Naming scheme for separate output face files. Defaults to the input naming scheme.
+    #[arg(value_name = "out-naming", long, value_enum)]
+    out_naming: Option<FaceNaming>,
+
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
+
+    /// Filter used to resample the input.
+    #[arg(value_name = "sampling", long, value_enum, default_value_t)]
+    sampling: Sampling,
+}
+
+impl Rotate {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let mut transform =
+            DirectionTransform::from_yaw_pitch_roll(self.yaw, self.pitch, self.roll);
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            transform = from.conversion(to).compose(transform);
+        }
+
+        if self.equirect {
+            let input = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+            let equirect = utilities::load_image(&deps, &input.path)?;
+            let rotated = transform.transform_equirect(&equirect, self.sampling);
+            let out_path = ImageOutput::resolve(
+                self.output.as_deref(),
+                self.out_base
+                    .unwrap_or_else(|| format!("{}-rotated", input.base)),
+                self.format.unwrap_or(input.format),
+            )?
+            .path();
+            utilities::write_image(&deps, &out_path, &rotated)?;
+            deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+            return Ok(());
+        }
+
+        let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-rotated", cube.base)),
+            self.format.unwrap_or(cube.format),
+        )?;
+        let sampling = if transform.is_axis_aligned() {
+            Sampling::Nearest
+        } else {
+            self.sampling
+        };
+        let rotated = transform.transform_faces(&cube.faces, sampling);
+        let written = utilities::write_cube(
+            &deps,
+            &output,
+            self.out_layout.unwrap_or(self.layout),
+            self.out_naming.unwrap_or(cube.naming),
+            false,
+            &rotated,
+        )?;
+        deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, FaceNaming},
+};
+use clap::Parser;
+use tyt_common::{ImageFormat, ImageInput, ImageOutput, split_extension};
+
+/// Projects a cubemap or equirectangular panorama onto L2 spherical harmonics.
+///
+/// Writes `{out_base}.json` (or `--output`) with nine RGB coefficients ordered `(l, m)` =
+/// `(0, 0)`, `(1, -1)`, `(1, 0)`, `(1, 1)`, `(2, -2)`, `(2, -1)`, `(2, 0)`, `(2, 1)`, `(2, 2)`, in
+/// linear color and the cubemap's right-handed, Y-up frame.
+#[derive(Clone, Debug, Parser)]
+pub struct Sh {
+    /// Input cubemap: the base name or path of any one separate face file (`{base}-left.png`,
+    /// etc.), or the image path or base name for single-image layouts and `--equirect`.
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-sh`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output JSON path. The preview is written beside it with an `-irradiance` suffix.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
+    /// Read the input as an equirectangular image instead of a cubemap.
+    #[arg(value_name = "equirect", long)]
+    equirect: bool,
+
+    /// How the input faces are arranged.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate input face files. Detected when omitted.
+    #[arg(value_name = "naming", long, value_enum)]
+    naming: Option<FaceNaming>,
+
+    /// Also render the irradiance as a cubemap to `{out_base}-irradiance`, with faces named like
+    /// the input's.
+    #[arg(value_name = "preview", long)]
+    preview: bool,
+
+    /// Side length in pixels for each preview face.
+    #[arg(value_name = "preview-size", long, default_value_t = 32)]
+    preview_size: u32,
+
+    /// Image format for the preview. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
+}
+
+impl Sh {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let (sh, in_base, in_format, naming) = if self.equirect {
+            let input = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+            let equirect = utilities::load_image(&deps, &input.path)?;
+            (
+                utilities::project_equirect_sh9(&equirect)?,
+                input.base,
+                input.format,
+                self.naming.unwrap_or_default(),
+            )
+        } else {
+            let cube = utilities::load_cube(&deps, &self.base, self.layout, self.naming)?;
+            (
+                utilities::project_faces_sh9(&cube.faces)?,
+                cube.base,
+                cube.format,
+                cube.naming,
+            )
+        };
+
+        let (out_base, json_path) = match self.output {
+            Some(output) => {
+                let out_base = split_extension(&output)
+                    .map_or(output.as_str(), |(stem, _)| stem)
+                    .to_owned();
+                (out_base, output)
+            }
+            None => {
+                let out_base = self.out_base.unwrap_or_else(|| format!("{in_base}-sh"));
+                let json_path = format!("{out_base}.json");
+                (out_base, json_path)
+            }
+        };
+        deps.write_file(&json_path, &deps.serialize_sh9_json(&sh)?)?;
+        deps.write_stdout(format!("Wrote: {json_path}\n").as_bytes())?;
+
+        if self.preview {
+            let faces = utilities::sh9_irradiance_faces(&sh, self.preview_size);
+            let written = utilities::write_cube(
+                &deps,
+                &ImageOutput::new(
+                    format!("{out_base}-irradiance"),
+                    self.format.unwrap_or(in_format),
+                ),
+                if self.equirect {
+                    CubeLayout::Faces
+                } else {
+                    self.layout
+                },
+                naming,
+                false,
+                &faces,
+            )?;
+            deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        }
+
+        Ok(())
+    }
+}
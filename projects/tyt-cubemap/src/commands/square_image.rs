@@ -1,26 +1,43 @@
-use crate::{Dependencies, Result, utilities};
+use crate::{
+    Dependencies, Result,
+    utilities::{self},
+};
 use clap::Parser;
+use tyt_common::{ImageFormat, ImageInput, ImageOutput};
 
 /// Pads an image to a square canvas with transparent background.
 #[derive(Clone, Debug, Parser)]
 pub struct SquareImage {
-    /// Base name for the input image (`{base}.png`).
+    /// Input image path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.exr`, etc.).
     #[arg(value_name = "base")]
     base: String,
 
     /// Output base name. Defaults to `{base}-square`.
     #[arg(value_name = "out-base")]
     out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Output image format. Defaults to the input's format.
+    #[arg(value_name = "format", long, value_enum)]
+    format: Option<ImageFormat>,
 }
 
 impl SquareImage {
     pub fn execute(self, deps: impl Dependencies) -> Result<()> {
-        let out_base = self
-            .out_base
-            .unwrap_or_else(|| format!("{}-square", self.base));
-        let in_path = format!("{}.png", self.base);
-        let out_path = format!("{out_base}.png");
-        utilities::square(&deps, &in_path, &out_path)?;
+        let input = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_path = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base
+                .unwrap_or_else(|| format!("{}-square", input.base)),
+            self.format.unwrap_or(input.format),
+        )?
+        .path();
+        let image = utilities::load_image(&deps, &input.path)?;
+        utilities::write_image(&deps, &out_path, &utilities::square_image(&image))?;
         deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
         Ok(())
     }
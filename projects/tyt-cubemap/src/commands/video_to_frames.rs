@@ -0,0 +1,176 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, Face, FaceNaming, FrameConversion, Sampling},
+};
+use clap::Parser;
+use std::path::Path;
+use tyt_common::{ImageFormat, ImageOutput, invalid_data, split_extension};
+
+/// Extracts frames from an equirectangular 360 video with ffmpeg, optionally converting each one
+/// to cube faces or a pixelated panorama.
+///
+/// Frame `i` is written to `{out_base}-{i}`, numbered from 0.
+#[derive(Clone, Debug, Parser)]
+pub struct VideoToFrames {
+    /// Path to the input video.
+    #[arg(value_name = "input")]
+    input: String,
+
+    /// Output base name. Defaults to the input path without its extension.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the image format taken from its extension. Frames are written as
+    /// `{stem}-{i}.{ext}`.
+    #[arg(value_name = "output", short, long, conflicts_with_all = ["out_base", "format"])]
+    output: Option<String>,
+
+    /// Frames per second to extract. Defaults to every frame of the video.
+    #[arg(value_name = "fps", long)]
+    fps: Option<f64>,
+
+    /// Time to start extracting at, in ffmpeg's time syntax (`90`, `1:30`, `00:01:30.5`).
+    #[arg(value_name = "start", long)]
+    start: Option<String>,
+
+    /// Time to stop extracting at, in ffmpeg's time syntax.
+    #[arg(value_name = "end", long, conflicts_with = "duration")]
+    end: Option<String>,
+
+    /// Length of video to extract, in ffmpeg's time syntax.
+    #[arg(value_name = "duration", long)]
+    duration: Option<String>,
+
+    /// What to convert each frame to.
+    #[arg(value_name = "convert", long, value_enum, default_value_t)]
+    convert: FrameConversion,
+
+    /// Side length in pixels of each face for `faces` (defaults to a quarter of the frame
+    /// width), or target height for `pixelated-equirect` (halved internally, default 256).
+    #[arg(value_name = "size", short, long)]
+    size: Option<u32>,
+
+    /// How the faces of each frame are arranged, with `--convert faces`.
+    #[arg(value_name = "layout", long, value_enum, default_value_t)]
+    layout: CubeLayout,
+
+    /// Naming scheme for separate face files, with `--convert faces`.
+    #[arg(value_name = "naming", long, value_enum, default_value_t)]
+    naming: FaceNaming,
+
+    /// Output image format.
+    #[arg(value_name = "format", long, value_enum, default_value_t)]
+    format: ImageFormat,
+
+    /// Filter used to reproject frames.
+    #[arg(value_name = "sampling", long, value_enum, default_value_t)]
+    sampling: Sampling,
+}
+
+impl VideoToFrames {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let input_base = split_extension(&self.input)
+            .map_or(self.input.as_str(), |(stem, _)| stem)
+            .to_owned();
+        let output = ImageOutput::resolve(
+            self.output.as_deref(),
+            self.out_base.clone().unwrap_or(input_base),
+            self.format,
+        )?;
+
+        let tmp_dir = deps.create_temp_dir()?;
+        let result = self.write_frames(&deps, &tmp_dir, &output);
+        deps.remove_dir_all(&tmp_dir)?;
+        let count = result?;
+        deps.write_stdout(format!("Extracted {count} frames\n").as_bytes())?;
+        Ok(())
+    }
+
+    /// Extracts frames into `tmp_dir`, then converts and writes each one, returning how many
+    /// were written.
+    fn write_frames(
+        &self,
+        deps: &impl Dependencies,
+        tmp_dir: &Path,
+        output: &ImageOutput,
+    ) -> Result<usize> {
+        let mut args: Vec<String> = vec!["-y".into(), "-i".into(), self.input.clone()];
+        if let Some(start) = &self.start {
+            args.extend(["-ss".into(), start.clone()]);
+        }
+        if let Some(end) = &self.end {
+            args.extend(["-to".into(), end.clone()]);
+        }
+        if let Some(duration) = &self.duration {
+            args.extend(["-t".into(), duration.clone()]);
+        }
+        if let Some(fps) = self.fps {
+            args.extend(["-vf".into(), format!("fps={fps}")]);
+        }
+        args.push(
+            tmp_dir
+                .join("frame-%06d.png")
+                .to_string_lossy()
+                .into_owned(),
+        );
+        deps.exec_ffmpeg(&args)?;
+
+        let frames = deps.glob_files(&tmp_dir.join("frame-*.png").to_string_lossy())?;
+        if frames.is_empty() {
+            return Err(invalid_data(format!(
+                "ffmpeg extracted no frames from {}",
+                self.input
+            )));
+        }
+
+        let digits = (frames.len() - 1).to_string().len();
+        for (i, frame_path) in frames.iter().enumerate() {
+            let frame_output = output.with_suffix(&format!("-{i:0digits$}"));
+            let written = self.write_frame(deps, frame_path, &frame_output)?;
+            deps.write_stdout(format!("Wrote: {written}\n").as_bytes())?;
+        }
+        Ok(frames.len())
+    }
+
+    /// Converts one extracted frame and writes it to `output`, returning a description of what
+    /// was written.
+    fn write_frame(
+        &self,
+        deps: &impl Dependencies,
+        frame_path: &Path,
+        output: &ImageOutput,
+    ) -> Result<String> {
+        let frame = utilities::load_image(deps, frame_path)?;
+        match self.convert {
+            FrameConversion::Equirect => {
+                let out_path = output.path();
+                utilities::write_image(deps, &out_path, &frame)?;
+                Ok(out_path)
+            }
+            FrameConversion::Faces => {
+                let size = self.size.unwrap_or(frame.width / 4).max(1);
+                let faces = Face::ALL.map(|face| {
+                    utilities::equirect_to_face_image(&frame, face, size, self.sampling)
+                });
+                utilities::write_cube(deps, output, self.layout, self.naming, false, &faces)
+            }
+            FrameConversion::PixelatedEquirect => {
+                let faces = Face::ALL.map(|face| {
+                    utilities::equirect_to_face_image(
+                        &frame,
+                        face,
+                        (frame.width / 4).max(1),
+                        self.sampling,
+                    )
+                });
+                let half_size = self.size.unwrap_or(256) / 2;
+                let pixelated = utilities::pixelate_faces(&faces, half_size);
+                let equirect =
+                    utilities::faces_to_equirect(deps, &pixelated, self.sampling, false)?;
+                let out_path = output.path();
+                utilities::write_image(deps, &out_path, &equirect)?;
+                Ok(out_path)
+            }
+        }
+    }
+}
@@ -1,11 +1,15 @@
-use crate::Result;
+use crate::{Result, SkyAnalysis, SkyDescription};
 use std::{
     ffi::OsStr,
     path::{Path, PathBuf},
 };
 
 /// Dependency injection trait for cubemap operations.
-pub trait Dependencies {
+///
+/// Implementations are shared across threads by the `batch` command.
+pub trait Dependencies: Clone + Sync {
+    fn create_dir_all<P: AsRef<Path>>(&self, path: P) -> Result<()>;
+
     fn create_temp_dir(&self) -> Result<PathBuf>;
 
     fn exec_ffmpeg<I, S>(&self, args: I) -> Result<Vec<u8>>
@@ -13,14 +17,50 @@ pub trait Dependencies {
         I: IntoIterator<Item = S>,
         S: AsRef<OsStr>;
 
-    fn exec_magick<I, S>(&self, args: I) -> Result<Vec<u8>>
-    where
-        I: IntoIterator<Item = S>,
-        S: AsRef<OsStr>;
+    fn file_exists<P: AsRef<Path>>(&self, path: P) -> Result<bool>;
+
+    /// Returns sorted file paths matching a glob pattern, or the files directly inside `pattern`
+    /// when it names a directory.
+    fn glob_files(&self, pattern: &str) -> Result<Vec<PathBuf>>;
+
+    /// Loads an image and converts it to RGBA32F, returning the pixel data, width, and height.
+    fn load_image_rgba32f<P: AsRef<Path>>(&self, path: P) -> Result<(Vec<f32>, u32, u32)>;
+
+    /// Parses a procedural sky description from JSON bytes.
+    fn parse_sky_description_json(&self, json: &[u8]) -> Result<SkyDescription>;
+
+    fn read_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>>;
 
     fn remove_dir_all<P: AsRef<Path>>(&self, path: P) -> Result<()>;
 
     fn rename_file<P1: AsRef<Path>, P2: AsRef<Path>>(&self, from: P1, to: P2) -> Result<()>;
 
+    /// Serializes a sky analysis to JSON bytes.
+    fn serialize_sky_analysis_json(&self, analysis: &SkyAnalysis) -> Result<Vec<u8>>;
+
+    /// Serializes L2 spherical harmonic RGB coefficients, ordered by band and then by `m`, to
+    /// JSON bytes.
+    fn serialize_sh9_json(&self, coefficients: &[[f64; 3]; 9]) -> Result<Vec<u8>>;
+
+    fn write_file<P: AsRef<Path>>(&self, path: P, contents: &[u8]) -> Result<()>;
+
+    /// Writes RGBA8 pixel data as an image, inferring the format from the path's extension.
+    fn write_image_rgba<P: AsRef<Path>>(
+        &self,
+        path: P,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<()>;
+
+    /// Writes RGBA32F pixel data as an image, inferring the format from the path's extension.
+    fn write_image_rgba32f<P: AsRef<Path>>(
+        &self,
+        path: P,
+        pixels: &[f32],
+        width: u32,
+        height: u32,
+    ) -> Result<()>;
+
     fn write_stdout(&self, contents: &[u8]) -> Result<()>;
 }
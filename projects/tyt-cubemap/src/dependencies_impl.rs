@@ -1,4 +1,4 @@
-use crate::{Dependencies, Error, Result};
+use crate::{Dependencies, Error, Result, SkyAnalysis, SkyDescription};
 use std::{
     ffi::OsStr,
     fs,
@@ -10,6 +10,10 @@ use std::{
 pub struct DependenciesImpl;
 
 impl Dependencies for DependenciesImpl {
+    fn create_dir_all<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Ok(fs::create_dir_all(path.as_ref())?)
+    }
+
     fn create_temp_dir(&self) -> Result<PathBuf> {
         Ok(tyt_injection::create_temp_dir()?)
     }
@@ -22,12 +26,24 @@ impl Dependencies for DependenciesImpl {
         tyt_injection::exec_map("ffmpeg", args, Error::IO, Error::Ffmpeg)
     }
 
-    fn exec_magick<I, S>(&self, args: I) -> Result<Vec<u8>>
-    where
-        I: IntoIterator<Item = S>,
-        S: AsRef<OsStr>,
-    {
-        tyt_injection::exec_map("magick", args, Error::IO, Error::Magick)
+    fn file_exists<P: AsRef<Path>>(&self, path: P) -> Result<bool> {
+        Ok(fs::exists(path.as_ref())?)
+    }
+
+    fn glob_files(&self, pattern: &str) -> Result<Vec<PathBuf>> {
+        Ok(tyt_injection::glob_files(pattern)?)
+    }
+
+    fn load_image_rgba32f<P: AsRef<Path>>(&self, path: P) -> Result<(Vec<f32>, u32, u32)> {
+        Ok(tyt_injection::load_image_rgba32f(path.as_ref())?)
+    }
+
+    fn parse_sky_description_json(&self, json: &[u8]) -> Result<SkyDescription> {
+        Ok(tyt_injection::parse_json(json)?)
+    }
+
+    fn read_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>> {
+        Ok(tyt_injection::read_file(path.as_ref())?)
     }
 
     fn remove_dir_all<P: AsRef<Path>>(&self, path: P) -> Result<()> {
@@ -38,6 +54,56 @@ impl Dependencies for DependenciesImpl {
         Ok(fs::rename(from.as_ref(), to.as_ref())?)
     }
 
+    fn serialize_sky_analysis_json(&self, analysis: &SkyAnalysis) -> Result<Vec<u8>> {
+        Ok(tyt_injection::serialize_sky_analysis_json(
+            analysis.sun_direction,
+            analysis.sun_intensity,
+            analysis.sun_color,
+            analysis.zenith,
+            analysis.horizon,
+            analysis.ground,
+            analysis.ambient,
+        )?)
+    }
+
+    fn serialize_sh9_json(&self, coefficients: &[[f64; 3]; 9]) -> Result<Vec<u8>> {
+        Ok(tyt_injection::serialize_sh9_json(coefficients)?)
+    }
+
+    fn write_file<P: AsRef<Path>>(&self, path: P, contents: &[u8]) -> Result<()> {
+        Ok(tyt_injection::write_file(path.as_ref(), contents)?)
+    }
+
+    fn write_image_rgba<P: AsRef<Path>>(
+        &self,
+        path: P,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<()> {
+        Ok(tyt_injection::write_image_rgba(
+            path.as_ref(),
+            pixels,
+            width,
+            height,
+        )?)
+    }
+
+    fn write_image_rgba32f<P: AsRef<Path>>(
+        &self,
+        path: P,
+        pixels: &[f32],
+        width: u32,
+        height: u32,
+    ) -> Result<()> {
+        Ok(tyt_injection::write_image_rgba32f(
+            path.as_ref(),
+            pixels,
+            width,
+            height,
+        )?)
+    }
+
     fn write_stdout(&self, contents: &[u8]) -> Result<()> {
         Ok(tyt_injection::write_stdout(contents)?)
     }
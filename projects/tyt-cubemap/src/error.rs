@@ -9,7 +9,6 @@ use tyt_common::ExecFailed;
 #[derive(Debug)]
 pub enum Error {
     Ffmpeg(ExecFailed),
-    Magick(ExecFailed),
     IO(IOError),
 }
 
@@ -33,23 +32,6 @@ impl Display for Error {
                 }
                 Ok(())
             }
-            Error::Magick(ExecFailed {
-                exit_code,
-                stdout,
-                stderr,
-            }) => {
-                match exit_code {
-                    Some(code) => write!(f, "magick exited with code {code}")?,
-                    None => write!(f, "magick killed by signal")?,
-                }
-                if !stdout.is_empty() {
-                    write!(f, "\nstdout:\n{stdout}")?;
-                }
-                if !stderr.is_empty() {
-                    write!(f, "\nstderr:\n{stderr}")?;
-                }
-                Ok(())
-            }
             Error::IO(e) => e.fmt(f),
         }
     }
@@ -58,7 +40,7 @@ impl Display for Error {
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self {
-            Error::Ffmpeg(_) | Error::Magick(_) => None,
+            Error::Ffmpeg(_) => None,
             Error::IO(e) => Some(e),
         }
     }
@@ -7,6 +7,7 @@ mod dependencies;
 mod dependencies_impl;
 mod error;
 mod result;
+mod sky_description;
 mod tyt_cubemap;
 
 pub use dependencies::*;
@@ -14,4 +15,6 @@ pub use dependencies::*;
 pub use dependencies_impl::*;
 pub use error::*;
 pub use result::*;
+pub use sky_description::*;
 pub use tyt_cubemap::*;
+pub use utilities::SkyAnalysis;
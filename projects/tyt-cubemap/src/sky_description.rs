@@ -0,0 +1,148 @@
+/// A procedural sky for `generate`, read from JSON. Colors are linear RGB and angles are in
+/// degrees, with azimuth turning right from the front and elevation rising from the horizon.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize))]
+#[cfg_attr(feature = "impl", serde(default, deny_unknown_fields))]
+pub struct SkyDescription {
+    /// Colors by elevation, blended linearly between stops.
+    pub gradient: Vec<GradientStop>,
+
+    pub sun: Option<SunDescription>,
+
+    pub stars: Option<StarsDescription>,
+
+    pub clouds: Option<CloudsDescription>,
+}
+
+impl Default for SkyDescription {
+    fn default() -> Self {
+        Self {
+            gradient: vec![
+                GradientStop {
+                    elevation: -90.0,
+                    color: [0.05, 0.04, 0.03],
+                },
+                GradientStop {
+                    elevation: 0.0,
+                    color: [0.7, 0.8, 0.9],
+                },
+                GradientStop {
+                    elevation: 90.0,
+                    color: [0.15, 0.3, 0.8],
+                },
+            ],
+            sun: None,
+            stars: None,
+            clouds: None,
+        }
+    }
+}
+
+/// The sky color at one elevation.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize))]
+#[cfg_attr(feature = "impl", serde(deny_unknown_fields))]
+pub struct GradientStop {
+    pub elevation: f64,
+    pub color: [f32; 3],
+}
+
+/// A sun disc with a glowing halo around it.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize))]
+#[cfg_attr(feature = "impl", serde(default, deny_unknown_fields))]
+pub struct SunDescription {
+    pub azimuth: f64,
+    pub elevation: f64,
+
+    /// Angular diameter of the disc.
+    pub size: f64,
+
+    /// Radiance of the disc, multiplied by `intensity`.
+    pub color: [f32; 3],
+    pub intensity: f32,
+
+    /// Angle over which the halo fades to about a third of its peak.
+    pub halo_size: f64,
+
+    /// Peak radiance of the halo, as a multiple of `color`.
+    pub halo_intensity: f32,
+}
+
+impl Default for SunDescription {
+    fn default() -> Self {
+        Self {
+            azimuth: 0.0,
+            elevation: 45.0,
+            size: 0.53,
+            color: [1.0, 0.95, 0.85],
+            intensity: 50.0,
+            halo_size: 8.0,
+            halo_intensity: 0.5,
+        }
+    }
+}
+
+/// Randomly placed single-texel stars.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize))]
+#[cfg_attr(feature = "impl", serde(default, deny_unknown_fields))]
+pub struct StarsDescription {
+    pub seed: u64,
+
+    /// Number of stars across the whole sphere.
+    pub count: u32,
+
+    /// Radiance of the brightest star over a 0.1 degree wide patch of sky. Each star's brightness
+    /// is drawn between zero and this.
+    pub brightness: f32,
+
+    /// Stars below this elevation are left out.
+    pub min_elevation: f64,
+}
+
+impl Default for StarsDescription {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            count: 2000,
+            brightness: 10.0,
+            min_elevation: 0.0,
+        }
+    }
+}
+
+/// Clouds from fractal value noise, fading out toward the horizon.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize))]
+#[cfg_attr(feature = "impl", serde(default, deny_unknown_fields))]
+pub struct CloudsDescription {
+    pub seed: u64,
+
+    /// Fraction of the sky covered, from 0 to 1.
+    pub coverage: f32,
+
+    /// Number of noise features across the sky. Larger values make smaller clouds.
+    pub scale: f64,
+
+    /// Number of noise octaves. More octaves add finer detail.
+    pub octaves: u32,
+
+    pub color: [f32; 3],
+
+    /// Opacity of fully covered sky, from 0 to 1.
+    pub opacity: f32,
+}
+
+impl Default for CloudsDescription {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            coverage: 0.4,
+            scale: 4.0,
+            octaves: 5,
+            color: [0.9, 0.9, 0.92],
+            opacity: 0.9,
+        }
+    }
+}
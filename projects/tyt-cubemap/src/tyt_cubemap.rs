@@ -17,8 +17,59 @@ pub enum TytCubemap {
     #[command(name = "equirect-to-faces")]
     EquirectToFaces(commands::EquirectToFaces),
 
+    #[command(name = "equirect-to-projection")]
+    EquirectToProjection(commands::EquirectToProjection),
+
+    #[command(name = "projection-to-equirect")]
+    ProjectionToEquirect(commands::ProjectionToEquirect),
+
+    #[command(name = "faces-to-net")]
+    FacesToNet(commands::FacesToNet),
+
+    #[command(name = "net-to-faces")]
+    NetToFaces(commands::NetToFaces),
+
+    #[command(name = "faces-to-container")]
+    FacesToContainer(commands::FacesToContainer),
+
+    #[command(name = "container-to-faces")]
+    ContainerToFaces(commands::ContainerToFaces),
+
+    #[command(name = "video-to-frames")]
+    VideoToFrames(commands::VideoToFrames),
+
     #[command(name = "pixelate-faces")]
     PixelateFaces(commands::PixelateFaces),
+
+    #[command(name = "check-seams")]
+    CheckSeams(commands::CheckSeams),
+
+    #[command(name = "fix-seams")]
+    FixSeams(commands::FixSeams),
+
+    #[command(name = "preview")]
+    Preview(commands::Preview),
+
+    #[command(name = "generate")]
+    Generate(commands::Generate),
+
+    #[command(name = "analyze")]
+    Analyze(commands::Analyze),
+
+    #[command(name = "prefilter")]
+    Prefilter(commands::Prefilter),
+
+    #[command(name = "rotate")]
+    Rotate(commands::Rotate),
+
+    #[command(name = "sh")]
+    Sh(commands::Sh),
+
+    #[command(name = "square-image")]
+    SquareImage(commands::SquareImage),
+
+    #[command(name = "batch")]
+    Batch(commands::Batch),
 }
 
 impl TytCubemap {
@@ -28,7 +79,24 @@ impl TytCubemap {
             TytCubemap::FacesToPixelatedEquirect(cmd) => cmd.execute(dependencies),
             TytCubemap::EquirectToNet(cmd) => cmd.execute(dependencies),
             TytCubemap::EquirectToFaces(cmd) => cmd.execute(dependencies),
+            TytCubemap::EquirectToProjection(cmd) => cmd.execute(dependencies),
+            TytCubemap::ProjectionToEquirect(cmd) => cmd.execute(dependencies),
+            TytCubemap::FacesToNet(cmd) => cmd.execute(dependencies),
+            TytCubemap::NetToFaces(cmd) => cmd.execute(dependencies),
+            TytCubemap::FacesToContainer(cmd) => cmd.execute(dependencies),
+            TytCubemap::ContainerToFaces(cmd) => cmd.execute(dependencies),
+            TytCubemap::VideoToFrames(cmd) => cmd.execute(dependencies),
             TytCubemap::PixelateFaces(cmd) => cmd.execute(dependencies),
+            TytCubemap::CheckSeams(cmd) => cmd.execute(dependencies),
+            TytCubemap::FixSeams(cmd) => cmd.execute(dependencies),
+            TytCubemap::Preview(cmd) => cmd.execute(dependencies),
+            TytCubemap::Generate(cmd) => cmd.execute(dependencies),
+            TytCubemap::Analyze(cmd) => cmd.execute(dependencies),
+            TytCubemap::Prefilter(cmd) => cmd.execute(dependencies),
+            TytCubemap::Rotate(cmd) => cmd.execute(dependencies),
+            TytCubemap::Sh(cmd) => cmd.execute(dependencies),
+            TytCubemap::SquareImage(cmd) => cmd.execute(dependencies),
+            TytCubemap::Batch(cmd) => cmd.execute(dependencies),
         }
     }
 }
@@ -0,0 +1,46 @@
+use crate::utilities::DirectionTransform;
+use clap::ValueEnum;
+
+/// A world coordinate convention that a cubemap is sampled in.
+///
+/// Each assumes the engine samples cube maps directly with world-space directions, using the
+/// standard OpenGL/Direct3D cube face layout. Face sets written by this tool are laid out for
+/// [`CoordinateSystem::YUpLh`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum CoordinateSystem {
+    /// Left-handed, `+X` right, `+Y` up, `+Z` forward (Unity, Direct3D).
+    YUpLh,
+
+    /// Right-handed, `+X` right, `+Y` up, `-Z` forward (OpenGL, glTF).
+    YUpRh,
+
+    /// Left-handed, `+X` forward, `+Y` right, `+Z` up (Unreal).
+    ZUpLh,
+
+    /// Right-handed, `+X` right, `+Y` forward, `+Z` up (Blender, 3ds Max).
+    ZUpRh,
+}
+
+impl CoordinateSystem {
+    /// Returns the transform that moves cubemap content laid out for `self` to where `to`
+    /// expects it, including any face swaps, flips, and rotations.
+    pub fn conversion(self, to: CoordinateSystem) -> DirectionTransform {
+        // Our frame mirrors X relative to the cube map frame, whose axes are (right, up, forward).
+        let mirror_x =
+            DirectionTransform::from_rows([[-1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        // Content sampled at world direction `w` in `self` must be sampled at `to`'s coordinates
+        // for the same physical direction.
+        let self_to_to = to.world_axes().compose(self.world_axes().inverse());
+        mirror_x.compose(self_to_to).compose(mirror_x)
+    }
+
+    /// Returns the matrix whose columns are the world-space right, up, and forward axes.
+    fn world_axes(self) -> DirectionTransform {
+        DirectionTransform::from_rows(match self {
+            CoordinateSystem::YUpLh => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            CoordinateSystem::YUpRh => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]],
+            CoordinateSystem::ZUpLh => [[0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            CoordinateSystem::ZUpRh => [[1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]],
+        })
+    }
+}
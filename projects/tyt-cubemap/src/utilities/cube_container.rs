@@ -0,0 +1,94 @@
+use crate::{
+    Result,
+    utilities::{Image, TexelFormat},
+};
+use clap::ValueEnum;
+use tyt_common::{
+    TextureLevels, decode_dds, decode_ktx2, encode_dds, encode_ktx2, invalid_data, is_dds, is_ktx2,
+};
+
+/// A single-file GPU texture container holding a whole cubemap.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum CubeContainer {
+    /// Khronos KTX2, as used by Vulkan, OpenGL, and glTF tooling.
+    #[default]
+    Ktx2,
+
+    /// DirectDraw Surface with a DX10 header, as used by Direct3D.
+    Dds,
+}
+
+impl CubeContainer {
+    /// Returns the file extension for this container, without a leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            CubeContainer::Ktx2 => "ktx2",
+            CubeContainer::Dds => "dds",
+        }
+    }
+
+    /// Returns the container whose extension is `ext`, ignoring case.
+    pub fn from_extension(ext: &str) -> Option<CubeContainer> {
+        [CubeContainer::Ktx2, CubeContainer::Dds]
+            .into_iter()
+            .find(|container| container.extension().eq_ignore_ascii_case(ext))
+    }
+
+    /// Encodes a cubemap mip chain, with `mips[0]` at full resolution and each level's faces
+    /// ordered as [`Face::ALL`].
+    ///
+    /// [`Face::ALL`]: crate::utilities::Face::ALL
+    pub fn encode(self, mips: &[[Image; 6]], format: TexelFormat) -> Vec<u8> {
+        let size = mips[0][0].width;
+        let texture = TextureLevels {
+            format: format.texture_format(),
+            width: size,
+            height: size,
+            faces: 6,
+            levels: mips
+                .iter()
+                .map(|faces| faces.iter().flat_map(|face| format.encode(face)).collect())
+                .collect(),
+        };
+        match self {
+            CubeContainer::Ktx2 => encode_ktx2(&texture),
+            CubeContainer::Dds => encode_dds(&texture),
+        }
+    }
+
+    /// Decodes a cubemap from either container, identified by its magic bytes, returning its
+    /// texel format and mip chain with faces ordered as [`Face::ALL`].
+    ///
+    /// [`Face::ALL`]: crate::utilities::Face::ALL
+    pub fn decode(bytes: &[u8]) -> Result<(TexelFormat, Vec<[Image; 6]>)> {
+        let texture = if is_ktx2(bytes) {
+            decode_ktx2(bytes)?
+        } else if is_dds(bytes) {
+            decode_dds(bytes)?
+        } else {
+            return Err(invalid_data("not a KTX2 or DDS file"));
+        };
+        if texture.faces != 6 || texture.width != texture.height {
+            return Err(invalid_data("file is not a cubemap"));
+        }
+
+        let (format, srgb) = TexelFormat::from_texture_format(texture.format);
+        let mips = texture
+            .levels
+            .iter()
+            .enumerate()
+            .map(|(level, texels)| {
+                let (size, _) = texture.level_size(level);
+                let mut faces: [Image; 6] = Default::default();
+                for (face, chunk) in faces
+                    .iter_mut()
+                    .zip(texels.chunks_exact(texture.face_length(level)))
+                {
+                    *face = format.decode(chunk, size, size, srgb);
+                }
+                faces
+            })
+            .collect();
+        Ok((format, mips))
+    }
+}
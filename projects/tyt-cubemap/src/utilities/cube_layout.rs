@@ -0,0 +1,156 @@
+use crate::utilities::{Face, Image};
+use clap::ValueEnum;
+
+/// How the six faces of a cubemap are arranged on disk.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum CubeLayout {
+    /// Six separate images, `{base}-{face}.png`.
+    #[default]
+    Faces,
+
+    /// The 4x3 cross written by `equirect-to-net`.
+    Net,
+
+    /// A 4x3 horizontal cross with the side faces in one row, as used by Unity.
+    #[value(name = "h-cross")]
+    HorizontalCross,
+
+    /// A 3x4 vertical cross with the back face below the down face.
+    #[value(name = "v-cross")]
+    VerticalCross,
+
+    /// A single row of six faces.
+    #[value(name = "6x1")]
+    Strip6x1,
+
+    /// A single column of six faces.
+    #[value(name = "1x6")]
+    Strip1x6,
+
+    /// Three columns by two rows, as produced by ffmpeg's `c3x2` output.
+    #[value(name = "3x2")]
+    C3x2,
+}
+
+/// Face placement in a grid layout: `(col, row, face, clockwise_quarter_turns)`.
+type Slots = &'static [(u32, u32, Face, u32)];
+
+const NET_SLOTS: Slots = &[
+    (1, 0, Face::Up, 3),
+    (0, 1, Face::Back, 0),
+    (1, 1, Face::Right, 0),
+    (2, 1, Face::Front, 0),
+    (3, 1, Face::Left, 0),
+    (1, 2, Face::Down, 1),
+];
+
+const HORIZONTAL_CROSS_SLOTS: Slots = &[
+    (1, 0, Face::Up, 0),
+    (0, 1, Face::Right, 0),
+    (1, 1, Face::Front, 0),
+    (2, 1, Face::Left, 0),
+    (3, 1, Face::Back, 0),
+    (1, 2, Face::Down, 0),
+];
+
+const VERTICAL_CROSS_SLOTS: Slots = &[
+    (1, 0, Face::Up, 0),
+    (0, 1, Face::Right, 0),
+    (1, 1, Face::Front, 0),
+    (2, 1, Face::Left, 0),
+    (1, 2, Face::Down, 0),
+    (1, 3, Face::Back, 2),
+];
+
+const STRIP_6X1_SLOTS: Slots = &[
+    (0, 0, Face::Left, 0),
+    (1, 0, Face::Right, 0),
+    (2, 0, Face::Up, 0),
+    (3, 0, Face::Down, 0),
+    (4, 0, Face::Front, 0),
+    (5, 0, Face::Back, 0),
+];
+
+const STRIP_1X6_SLOTS: Slots = &[
+    (0, 0, Face::Left, 0),
+    (0, 1, Face::Right, 0),
+    (0, 2, Face::Up, 0),
+    (0, 3, Face::Down, 0),
+    (0, 4, Face::Front, 0),
+    (0, 5, Face::Back, 0),
+];
+
+const C3X2_SLOTS: Slots = &[
+    (0, 0, Face::Left, 0),
+    (1, 0, Face::Right, 0),
+    (2, 0, Face::Up, 0),
+    (0, 1, Face::Down, 0),
+    (1, 1, Face::Front, 0),
+    (2, 1, Face::Back, 0),
+];
+
+impl CubeLayout {
+    /// Returns `(cols, rows, slots)` for single-image layouts, or `None` for [`CubeLayout::Faces`].
+    fn grid(self) -> Option<(u32, u32, Slots)> {
+        match self {
+            CubeLayout::Faces => None,
+            CubeLayout::Net => Some((4, 3, NET_SLOTS)),
+            CubeLayout::HorizontalCross => Some((4, 3, HORIZONTAL_CROSS_SLOTS)),
+            CubeLayout::VerticalCross => Some((3, 4, VERTICAL_CROSS_SLOTS)),
+            CubeLayout::Strip6x1 => Some((6, 1, STRIP_6X1_SLOTS)),
+            CubeLayout::Strip1x6 => Some((1, 6, STRIP_1X6_SLOTS)),
+            CubeLayout::C3x2 => Some((3, 2, C3X2_SLOTS)),
+        }
+    }
+
+    /// Returns `true` if this layout stores all six faces in one image.
+    pub fn is_single_image(self) -> bool {
+        self.grid().is_some()
+    }
+
+    /// Assembles six square faces, ordered as [`Face::ALL`], into one transparent image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on [`CubeLayout::Faces`].
+    pub fn pack(self, faces: &[Image; 6]) -> Image {
+        let (cols, rows, slots) = self.grid().expect("layout has no single-image form");
+        let size = faces[0].width;
+        let mut out = Image::new(cols * size, rows * size);
+        for &(col, row, face, turns) in slots {
+            let rotated = faces[face.index()].rotate_cw(turns);
+            out.blit(&rotated, col * size, row * size);
+        }
+        out
+    }
+
+    /// Slices one image into six faces, ordered as [`Face::ALL`], undoing any slot rotations.
+    ///
+    /// The grid may also be centered on a square canvas, as written by [`square_image`]. Returns
+    /// `None` if the image's dimensions do not fit this layout's grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on [`CubeLayout::Faces`].
+    ///
+    /// [`square_image`]: crate::utilities::square_image
+    pub fn unpack(self, image: &Image) -> Option<[Image; 6]> {
+        let (cols, rows, slots) = self.grid().expect("layout has no single-image form");
+        let size = image.width.max(image.height) / cols.max(rows);
+        let (grid_width, grid_height) = (cols * size, rows * size);
+        let fits = image.width == grid_width && image.height == grid_height;
+        let fits_square = image.width == image.height && image.width == grid_width.max(grid_height);
+        if size == 0 || !(fits || fits_square) {
+            return None;
+        }
+        let x0 = (image.width - grid_width) / 2;
+        let y0 = (image.height - grid_height) / 2;
+        let mut faces: [Image; 6] = Default::default();
+        for &(col, row, face, turns) in slots {
+            faces[face.index()] = image
+                .crop(x0 + col * size, y0 + row * size, size, size)
+                .rotate_cw(4 - turns);
+        }
+        Some(faces)
+    }
+}
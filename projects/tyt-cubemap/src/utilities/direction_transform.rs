@@ -0,0 +1,105 @@
+use crate::utilities::{
+    Face, Image, Sampling, equirect_direction, face_texel_direction, sample_equirect, sample_faces,
+};
+use ty_math::TyVector3;
+
+/// An orthogonal 3x3 matrix (a rotation, possibly combined with a mirror) applied to directions
+/// in the cubemap frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirectionTransform {
+    rows: [[f64; 3]; 3],
+}
+
+impl DirectionTransform {
+    /// Creates a transform from its rows.
+    pub const fn from_rows(rows: [[f64; 3]; 3]) -> Self {
+        Self { rows }
+    }
+
+    /// Returns the rotation that turns content by `yaw`, `pitch`, and `roll` degrees, applied in
+    /// that order from the viewer's point of view: roll first, then pitch, then yaw.
+    ///
+    /// Positive yaw moves content right (toward increasing equirect longitude), positive pitch
+    /// moves the front of the panorama up, and positive roll turns content clockwise as seen from
+    /// the center looking at the front.
+    pub fn from_yaw_pitch_roll(yaw: f64, pitch: f64, roll: f64) -> Self {
+        let (sy, cy) = (-yaw.to_radians()).sin_cos();
+        let (sp, cp) = (-pitch.to_radians()).sin_cos();
+        let (sr, cr) = roll.to_radians().sin_cos();
+        let yaw = Self::from_rows([[cy, 0.0, sy], [0.0, 1.0, 0.0], [-sy, 0.0, cy]]);
+        let pitch = Self::from_rows([[1.0, 0.0, 0.0], [0.0, cp, -sp], [0.0, sp, cp]]);
+        let roll = Self::from_rows([[cr, -sr, 0.0], [sr, cr, 0.0], [0.0, 0.0, 1.0]]);
+        yaw.compose(pitch).compose(roll)
+    }
+
+    /// Returns the transform that applies `other` and then `self`.
+    pub fn compose(self, other: DirectionTransform) -> Self {
+        let mut rows = [[0.0; 3]; 3];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.rows[i][k] * other.rows[k][j]).sum();
+            }
+        }
+        Self { rows }
+    }
+
+    /// Returns the inverse transform, which for an orthogonal matrix is its transpose.
+    pub fn inverse(self) -> Self {
+        let r = self.rows;
+        Self::from_rows([
+            [r[0][0], r[1][0], r[2][0]],
+            [r[0][1], r[1][1], r[2][1]],
+            [r[0][2], r[1][2], r[2][2]],
+        ])
+    }
+
+    /// Applies the transform to `dir`.
+    pub fn apply(self, dir: TyVector3) -> TyVector3 {
+        let [x, y, z] = self
+            .rows
+            .map(|r| r[0] * dir.x + r[1] * dir.y + r[2] * dir.z);
+        TyVector3::new(x, y, z)
+    }
+
+    /// Returns `true` if every entry is within rounding of `-1`, `0`, or `1`, meaning the transform
+    /// only permutes and flips axes and so maps texel centers onto texel centers.
+    pub fn is_axis_aligned(self) -> bool {
+        self.rows
+            .iter()
+            .flatten()
+            .all(|v| (v.abs() - v.abs().round()).abs() < 1e-9)
+    }
+
+    /// Resamples six cube faces, ordered as [`Face::ALL`], so their content is moved by this
+    /// transform.
+    pub fn transform_faces(self, faces: &[Image; 6], sampling: Sampling) -> [Image; 6] {
+        let inverse = self.inverse();
+        let size = faces[0].width;
+        Face::ALL.map(|face| {
+            let mut out = Image::new(size, size);
+            for y in 0..size {
+                for x in 0..size {
+                    let dir = inverse.apply(face_texel_direction(face, x, y, size));
+                    out.set(x, y, sample_faces(faces, dir, sampling));
+                }
+            }
+            out
+        })
+    }
+
+    /// Resamples an equirectangular image so its content is moved by this transform.
+    pub fn transform_equirect(self, equirect: &Image, sampling: Sampling) -> Image {
+        let inverse = self.inverse();
+        let (width, height) = (equirect.width, equirect.height);
+        let mut out = Image::new(width, height);
+        for y in 0..height {
+            let v = (y as f64 + 0.5) / height as f64;
+            for x in 0..width {
+                let u = (x as f64 + 0.5) / width as f64;
+                let dir = inverse.apply(equirect_direction(u, v));
+                out.set(x, y, sample_equirect(equirect, dir, sampling));
+            }
+        }
+        out
+    }
+}
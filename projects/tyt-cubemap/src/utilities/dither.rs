@@ -0,0 +1,116 @@
+use crate::utilities::{Face, Image};
+use tyt_common::{
+    DiffusionWeight, Dither, MAX_DIFFUSION_NEIGHBORS, Palette, linear_to_srgb, srgb_to_linear,
+};
+
+/// Reduces six cube faces, ordered as [`Face::ALL`], to the colors of `palette`.
+///
+/// Dithering works on sRGB-encoded color and matching happens in Oklab, and alpha is left
+/// untouched. Error diffusion treats the cube as
+/// one surface: error pushed past a face edge lands on the touching texel of the neighboring
+/// face, and the weights of neighbors that were already visited go to the remaining ones, so no
+/// error piles up or vanishes along seams.
+pub fn quantize_faces(faces: &mut [Image; 6], palette: &Palette, dither: Dither) {
+    if let Some(bits) = dither.bayer_bits() {
+        let spread = palette.mean_spacing();
+        for face in faces.iter_mut() {
+            for y in 0..face.height {
+                for x in 0..face.width {
+                    let offset = (tyt_common::bayer_threshold(x, y, bits) - 0.5) * spread;
+                    let mut pixel = face.get(x, y);
+                    let color = encode(&pixel).map(|c| c + offset);
+                    set_color(&mut pixel, palette.nearest_color(color));
+                    face.set(x, y, pixel);
+                }
+            }
+        }
+    } else if let Some(kernel) = dither.kernel() {
+        diffuse_error(faces, palette, kernel);
+    } else {
+        for face in faces.iter_mut() {
+            for pixel in &mut face.pixels {
+                set_color(pixel, palette.nearest_color(encode(pixel)));
+            }
+        }
+    }
+}
+
+fn diffuse_error(faces: &mut [Image; 6], palette: &Palette, kernel: &[DiffusionWeight]) {
+    let size = faces[0].width;
+    let texels = size as usize * size as usize;
+    let mut error = vec![[0.0f32; 3]; 6 * texels];
+    let mut visited = vec![false; 6 * texels];
+    // The share of each texel's error the kernel passes on, which Atkinson keeps below one.
+    let kept: f32 = kernel.iter().map(|&(_, _, weight)| weight).sum();
+    let index = |face: Face, x: u32, y: u32| face.index() * texels + (y * size + x) as usize;
+
+    for face in Face::ALL {
+        for y in 0..size {
+            // Alternate scan direction each row so error doesn't drift to one side.
+            let forward: i64 = if y % 2 == 0 { 1 } else { -1 };
+            for i in 0..size {
+                let x = if forward > 0 { i } else { size - 1 - i };
+                let here = index(face, x, y);
+                visited[here] = true;
+
+                let mut pixel = faces[face.index()].get(x, y);
+                let wanted = encode(&pixel);
+                let color = [0, 1, 2].map(|c| (wanted[c] + error[here][c]).clamp(0.0, 1.0));
+                let chosen = palette.nearest_color(color);
+                set_color(&mut pixel, chosen);
+                faces[face.index()].set(x, y, pixel);
+
+                let mut targets = [(0, 0.0); MAX_DIFFUSION_NEIGHBORS];
+                let mut count = 0;
+                for &(dx, dy, weight) in kernel {
+                    let Some((face, x, y)) =
+                        neighbor(face, x as i64 + dx * forward, y as i64 + dy, size)
+                    else {
+                        continue;
+                    };
+                    let target = index(face, x, y);
+                    if !visited[target] {
+                        targets[count] = (target, weight);
+                        count += 1;
+                    }
+                }
+                let targets = &targets[..count];
+                let total: f32 = targets.iter().map(|&(_, w)| w).sum();
+                for &(target, weight) in targets {
+                    for c in 0..3 {
+                        error[target][c] += (color[c] - chosen[c]) * weight * kept / total;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the texel at `(x, y)` on `face`, following the cube onto a neighboring face when the
+/// position is one texel past an edge. Positions past a corner have no single neighbor.
+fn neighbor(face: Face, x: i64, y: i64, size: u32) -> Option<(Face, u32, u32)> {
+    let n = size as i64;
+    let inside = |c: i64| (0..n).contains(&c);
+    match (inside(x), inside(y)) {
+        (true, true) => Some((face, x as u32, y as u32)),
+        (false, false) => None,
+        _ => {
+            let to_face = |c: i64| 2.0 * (c as f64 + 0.5) / size as f64 - 1.0;
+            let (neighbor, u, v) = Face::from_direction(face.direction(to_face(x), to_face(y)));
+            let to_texel = |c: f64| (((c + 1.0) * 0.5 * size as f64) as u32).min(size - 1);
+            Some((neighbor, to_texel(u), to_texel(v)))
+        }
+    }
+}
+
+/// Returns the sRGB-encoded color channels of a linear-light pixel.
+fn encode(pixel: &[f32; 4]) -> [f32; 3] {
+    [0, 1, 2].map(|c| linear_to_srgb(pixel[c].clamp(0.0, 1.0)))
+}
+
+/// Replaces the color channels of a linear-light pixel with an sRGB-encoded color.
+fn set_color(pixel: &mut [f32; 4], color: [f32; 3]) {
+    for c in 0..3 {
+        pixel[c] = srgb_to_linear(color[c]);
+    }
+}
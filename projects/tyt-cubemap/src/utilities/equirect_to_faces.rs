@@ -0,0 +1,60 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, Face, Image, Sampling},
+};
+use std::{io::Error as IOError, io::ErrorKind, path::Path};
+
+/// Reprojects the equirectangular image at `in_path` onto six `size` x `size` cube faces,
+/// ordered as [`Face::ALL`].
+///
+/// Uses the native reprojection engine unless `use_ffmpeg` is set.
+pub fn equirect_to_faces(
+    deps: &impl Dependencies,
+    in_path: &str,
+    size: u32,
+    sampling: Sampling,
+    use_ffmpeg: bool,
+) -> Result<[Image; 6]> {
+    if !use_ffmpeg {
+        let equirect = utilities::load_image(deps, in_path)?;
+        return Ok(Face::ALL
+            .map(|face| utilities::equirect_to_face_image(&equirect, face, size, sampling)));
+    }
+
+    let tmp_dir = deps.create_temp_dir()?;
+    let result = equirect_to_faces_ffmpeg(deps, in_path, size, sampling, &tmp_dir);
+    deps.remove_dir_all(&tmp_dir)?;
+    result
+}
+
+/// Converts the panorama to a c3x2 image with ffmpeg's `v360` filter and slices it.
+fn equirect_to_faces_ffmpeg(
+    deps: &impl Dependencies,
+    in_path: &str,
+    size: u32,
+    sampling: Sampling,
+    tmp_dir: &Path,
+) -> Result<[Image; 6]> {
+    let c3x2_path = tmp_dir.join("c3x2.png");
+    let c3x2_str = c3x2_path.to_string_lossy().into_owned();
+
+    let vf = format!(
+        "v360=input=equirect:output=c3x2:interp={},scale={}:{}:flags=neighbor",
+        sampling.ffmpeg_interp(),
+        3 * size,
+        2 * size
+    );
+    deps.exec_ffmpeg(["-y", "-i", in_path, "-vf", &vf, &c3x2_str])?;
+
+    let c3x2 = utilities::load_image(deps, &c3x2_path)?;
+    CubeLayout::C3x2.unpack(&c3x2).ok_or_else(|| {
+        IOError::new(
+            ErrorKind::InvalidData,
+            format!(
+                "ffmpeg produced a {}x{} c3x2 image",
+                c3x2.width, c3x2.height
+            ),
+        )
+        .into()
+    })
+}
@@ -0,0 +1,91 @@
+use ty_math::TyVector3;
+
+/// A cube face, named for the axis it looks along in a right-handed, Y-up frame where the
+/// equirectangular center is `+Z`.
+///
+/// Face images are viewed from inside the cube and match the orientation of ffmpeg's `v360`
+/// cubemap outputs, so native and ffmpeg results are interchangeable. Names match Unity's
+/// six-sided skybox slots, so `left` is the face a viewer looking at `front` sees on their right.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Face {
+    Left,
+    Right,
+    Up,
+    Down,
+    Front,
+    Back,
+}
+
+impl Face {
+    /// All faces in the order they appear in a c6x1 strip.
+    ///
+    /// This is also the `+X`, `-X`, `+Y`, `-Y`, `+Z`, `-Z` layer order of KTX2 and DDS cube maps,
+    /// whose left-handed frame mirrors X relative to ours.
+    pub const ALL: [Face; 6] = [
+        Face::Left,
+        Face::Right,
+        Face::Up,
+        Face::Down,
+        Face::Front,
+        Face::Back,
+    ];
+
+    /// Returns the position of this face in [`Face::ALL`].
+    pub fn index(self) -> usize {
+        match self {
+            Face::Left => 0,
+            Face::Right => 1,
+            Face::Up => 2,
+            Face::Down => 3,
+            Face::Front => 4,
+            Face::Back => 5,
+        }
+    }
+
+    /// Returns the file name suffix for this face (`{base}-{name}.png`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Face::Left => "left",
+            Face::Right => "right",
+            Face::Up => "up",
+            Face::Down => "down",
+            Face::Front => "front",
+            Face::Back => "back",
+        }
+    }
+
+    /// Returns the (unnormalized) direction through face coordinates `u` and `v`, each in
+    /// `[-1, 1]` with `u` pointing right and `v` pointing down in the face image.
+    pub fn direction(self, u: f64, v: f64) -> TyVector3 {
+        match self {
+            Face::Right => TyVector3::new(1.0, -v, u),
+            Face::Left => TyVector3::new(-1.0, -v, -u),
+            Face::Up => TyVector3::new(-u, 1.0, v),
+            Face::Down => TyVector3::new(-u, -1.0, -v),
+            Face::Front => TyVector3::new(-u, -v, 1.0),
+            Face::Back => TyVector3::new(u, -v, -1.0),
+        }
+    }
+
+    /// Returns the face a direction points into along with its face coordinates `(u, v)`.
+    pub fn from_direction(dir: TyVector3) -> (Face, f64, f64) {
+        let (ax, ay, az) = (dir.x.abs(), dir.y.abs(), dir.z.abs());
+        if ax >= ay && ax >= az {
+            if dir.x > 0.0 {
+                (Face::Right, dir.z / ax, -dir.y / ax)
+            } else {
+                (Face::Left, -dir.z / ax, -dir.y / ax)
+            }
+        } else if ay >= az {
+            if dir.y > 0.0 {
+                (Face::Up, -dir.x / ay, dir.z / ay)
+            } else {
+                (Face::Down, -dir.x / ay, -dir.z / ay)
+            }
+        } else if dir.z > 0.0 {
+            (Face::Front, -dir.x / az, -dir.y / az)
+        } else {
+            (Face::Back, dir.x / az, -dir.y / az)
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{Face, FaceNaming},
+};
+use std::io::{Error as IOError, ErrorKind};
+use tyt_common::{ImageFormat, split_extension};
+
+/// A complete set of separate face files: `{base}-{name}.{extension}`.
+#[derive(Clone, Debug)]
+pub struct FaceFiles {
+    pub base: String,
+    pub naming: FaceNaming,
+    pub extension: String,
+    pub format: ImageFormat,
+}
+
+impl FaceFiles {
+    /// Locates the faces for `input`, which is either the path of any one face file or a base
+    /// name. Only `naming` is tried when it is given.
+    pub fn locate(
+        deps: &impl Dependencies,
+        input: &str,
+        naming: Option<FaceNaming>,
+    ) -> Result<Self> {
+        let namings = match naming {
+            Some(naming) => vec![naming],
+            None => FaceNaming::ALL.to_vec(),
+        };
+
+        if let Some((stem, extension)) = split_extension(input)
+            && let Some(format) = ImageFormat::from_extension(extension)
+            && deps.file_exists(input)?
+        {
+            for &naming in &namings {
+                for face in Face::ALL {
+                    let suffix = format!("-{}", naming.face_name(face));
+                    if let Some(base) = stem.strip_suffix(&suffix) {
+                        let files = Self {
+                            base: base.to_owned(),
+                            naming,
+                            extension: extension.to_owned(),
+                            format,
+                        };
+                        if files.all_exist(deps)? {
+                            return Ok(files);
+                        }
+                    }
+                }
+            }
+        }
+
+        for &naming in &namings {
+            for format in ImageFormat::ALL {
+                for extension in format.extensions() {
+                    let files = Self {
+                        base: input.to_owned(),
+                        naming,
+                        extension: (*extension).to_owned(),
+                        format,
+                    };
+                    if files.all_exist(deps)? {
+                        return Ok(files);
+                    }
+                }
+            }
+        }
+        Err(IOError::new(
+            ErrorKind::NotFound,
+            format!("no complete set of faces found for `{input}`"),
+        )
+        .into())
+    }
+
+    /// Returns the path of `face`.
+    pub fn path(&self, face: Face) -> String {
+        self.naming.face_path(&self.base, face, &self.extension)
+    }
+
+    fn all_exist(&self, deps: &impl Dependencies) -> Result<bool> {
+        for face in Face::ALL {
+            if !deps.file_exists(self.path(face))? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
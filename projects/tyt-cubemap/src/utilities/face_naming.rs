@@ -0,0 +1,52 @@
+use crate::utilities::Face;
+use clap::ValueEnum;
+
+/// How individual face files are named (`{base}-{name}.{ext}`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum FaceNaming {
+    /// `left`, `right`, `up`, `down`, `front`, `back`.
+    #[default]
+    Words,
+
+    /// `posx`, `negx`, `posy`, `negy`, `posz`, `negz`.
+    ///
+    /// Axis names follow the left-handed cube map frame of OpenGL, Vulkan, and Direct3D, which
+    /// mirrors X, so `posx` is the `left` face.
+    Posx,
+
+    /// `px`, `nx`, `py`, `ny`, `pz`, `nz`, with the same axes as `posx`.
+    Px,
+}
+
+impl FaceNaming {
+    /// All naming schemes, in the order they are tried by [`FaceFiles::locate`](crate::utilities::FaceFiles::locate).
+    pub const ALL: [FaceNaming; 3] = [FaceNaming::Words, FaceNaming::Posx, FaceNaming::Px];
+
+    /// Returns the name of `face` under this scheme.
+    pub fn face_name(self, face: Face) -> &'static str {
+        match self {
+            FaceNaming::Words => face.name(),
+            FaceNaming::Posx => match face {
+                Face::Left => "posx",
+                Face::Right => "negx",
+                Face::Up => "posy",
+                Face::Down => "negy",
+                Face::Front => "posz",
+                Face::Back => "negz",
+            },
+            FaceNaming::Px => match face {
+                Face::Left => "px",
+                Face::Right => "nx",
+                Face::Up => "py",
+                Face::Down => "ny",
+                Face::Front => "pz",
+                Face::Back => "nz",
+            },
+        }
+    }
+
+    /// Returns the path of `face` for the face set `base` stored with file extension `extension`.
+    pub fn face_path(self, base: &str, face: Face, extension: &str) -> String {
+        format!("{base}-{}.{extension}", self.face_name(face))
+    }
+}
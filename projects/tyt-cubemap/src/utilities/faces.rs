@@ -0,0 +1,103 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, Face, FaceFiles, FaceNaming, Image},
+};
+use clap::ValueEnum;
+use std::io::{Error as IOError, ErrorKind};
+use tyt_common::{ImageFormat, ImageInput, ImageOutput};
+
+/// A loaded cubemap along with where and how it was stored.
+#[derive(Clone, Debug)]
+pub struct CubeInput {
+    /// The input path without its extension (and face suffix), used to name outputs.
+    pub base: String,
+
+    /// Faces ordered as [`Face::ALL`].
+    pub faces: [Image; 6],
+
+    pub format: ImageFormat,
+
+    /// The naming scheme of separate face files, or the default for single-image layouts.
+    pub naming: FaceNaming,
+}
+
+/// Loads a cubemap stored as `layout` from `input`, which is either a file path or a base name.
+///
+/// Separate faces are read from `{base}-{face}.{ext}`, detecting the naming scheme when `naming`
+/// is `None`; the path of any one face also works. Single-image layouts are read through
+/// [`ImageInput::locate`].
+pub fn load_cube(
+    deps: &impl Dependencies,
+    input: &str,
+    layout: CubeLayout,
+    naming: Option<FaceNaming>,
+) -> Result<CubeInput> {
+    if !layout.is_single_image() {
+        let files = FaceFiles::locate(deps, input, naming)?;
+        let mut faces: [Image; 6] = Default::default();
+        for face in Face::ALL {
+            faces[face.index()] = utilities::load_image(deps, files.path(face))?;
+        }
+        return Ok(CubeInput {
+            base: files.base,
+            faces,
+            format: files.format,
+            naming: files.naming,
+        });
+    }
+
+    let input = ImageInput::locate(input, |path| deps.file_exists(path))?;
+    let image = utilities::load_image(deps, &input.path)?;
+    let faces = layout.unpack(&image).ok_or_else(|| {
+        let layout = layout.to_possible_value().expect("no skipped variants");
+        IOError::new(
+            ErrorKind::InvalidData,
+            format!(
+                "{} is {}x{}, which does not fit the `{}` layout",
+                input.path,
+                image.width,
+                image.height,
+                layout.get_name()
+            ),
+        )
+    })?;
+    Ok(CubeInput {
+        base: input.base,
+        faces,
+        format: input.format,
+        naming: naming.unwrap_or_default(),
+    })
+}
+
+/// Writes faces, ordered as [`Face::ALL`], as a cubemap stored as `layout` at `output`,
+/// returning a description of what was written.
+///
+/// Separate faces are written to `{base}-{face}.{ext}`. Single-image layouts are padded to a
+/// square canvas when `square` is set.
+pub fn write_cube(
+    deps: &impl Dependencies,
+    output: &ImageOutput,
+    layout: CubeLayout,
+    naming: FaceNaming,
+    square: bool,
+    faces: &[Image; 6],
+) -> Result<String> {
+    if !layout.is_single_image() {
+        for face in Face::ALL {
+            utilities::write_image(
+                deps,
+                naming.face_path(&output.base, face, &output.extension),
+                &faces[face.index()],
+            )?;
+        }
+        return Ok(format!("{}-*.{}", output.base, output.extension));
+    }
+
+    let mut image = layout.pack(faces);
+    if square {
+        image = utilities::square_image(&image);
+    }
+    let out_path = output.path();
+    utilities::write_image(deps, &out_path, &image)?;
+    Ok(out_path)
+}
@@ -1,26 +1,48 @@
-use crate::{Dependencies, Result};
+use crate::{
+    Dependencies, Result,
+    utilities::{self, CubeLayout, Image, Sampling},
+};
 use std::path::Path;
 
-const FACES: &[&str] = &["left", "right", "up", "down", "front", "back"];
-
-/// Appends six cube face images into a horizontal strip and converts to equirectangular.
+/// Converts six cube faces, ordered as [`Face::ALL`](crate::utilities::Face::ALL), into an
+/// equirectangular panorama four faces wide and two faces tall.
+///
+/// Uses the native reprojection engine unless `use_ffmpeg` is set.
 pub fn faces_to_equirect(
     deps: &impl Dependencies,
-    base: &str,
-    out_base: &str,
+    faces: &[Image; 6],
+    sampling: Sampling,
+    use_ffmpeg: bool,
+) -> Result<Image> {
+    let size = faces[0].height;
+    if !use_ffmpeg {
+        return Ok(utilities::faces_to_equirect_image(
+            faces,
+            4 * size,
+            2 * size,
+            sampling,
+        ));
+    }
+
+    let tmp_dir = deps.create_temp_dir()?;
+    let result = faces_to_equirect_ffmpeg(deps, faces, sampling, &tmp_dir);
+    deps.remove_dir_all(&tmp_dir)?;
+    result
+}
+
+/// Writes the faces as a c6x1 strip and converts it with ffmpeg's `v360` filter.
+fn faces_to_equirect_ffmpeg(
+    deps: &impl Dependencies,
+    faces: &[Image; 6],
+    sampling: Sampling,
     tmp_dir: &Path,
-) -> Result<String> {
+) -> Result<Image> {
     let strip_path = tmp_dir.join("strip.png");
     let strip_str = strip_path.to_string_lossy().into_owned();
-    let out_path = format!("{out_base}.png");
+    let equirect_path = tmp_dir.join("equirect.png");
+    let equirect_str = equirect_path.to_string_lossy().into_owned();
 
-    let mut magick_args: Vec<String> = FACES
-        .iter()
-        .map(|face| format!("{base}-{face}.png"))
-        .collect();
-    magick_args.push("+append".into());
-    magick_args.push(strip_str.clone());
-    deps.exec_magick(magick_args)?;
+    utilities::write_image(deps, &strip_path, &CubeLayout::Strip6x1.pack(faces))?;
 
     deps.exec_ffmpeg([
         "-y",
@@ -29,9 +51,9 @@ pub fn faces_to_equirect(
         "-i",
         &strip_str,
         "-vf",
-        "v360=c6x1:e",
-        &out_path,
+        &format!("v360=c6x1:e:interp={}", sampling.ffmpeg_interp()),
+        &equirect_str,
     ])?;
 
-    Ok(out_path)
+    utilities::load_image(deps, &equirect_path)
 }
@@ -0,0 +1,16 @@
+use clap::ValueEnum;
+
+/// What each frame extracted from an equirectangular video is written as.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum FrameConversion {
+    /// The equirectangular frame as is.
+    #[default]
+    Equirect,
+
+    /// Six cube faces.
+    Faces,
+
+    /// An equirectangular panorama rebuilt from pixelated cube faces, as
+    /// `faces-to-pixelated-equirect` does.
+    PixelatedEquirect,
+}
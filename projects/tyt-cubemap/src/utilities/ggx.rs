@@ -0,0 +1,105 @@
+use crate::utilities::{self, Face, Image, Sampling};
+use std::f64::consts::{PI, TAU};
+use ty_math::TyVector3;
+
+/// Returns a box-filtered mip chain of six faces, ordered as [`Face::ALL`], down to 1x1.
+pub fn face_mip_chain(faces: &[Image; 6]) -> Vec<[Image; 6]> {
+    let mut chain = vec![faces.clone()];
+    while chain.last().expect("chain is non-empty")[0].width > 1 {
+        let next = chain.last().expect("chain is non-empty").each_ref();
+        chain.push(next.map(Image::downsample_box));
+    }
+    chain
+}
+
+/// Convolves a cubemap with a GGX lobe of the given perceptual `roughness`, producing six
+/// `size` x `size` faces ordered as [`Face::ALL`].
+///
+/// `source_mips` is the mip chain of the source cubemap (see [`face_mip_chain`]). Each output
+/// texel integrates `samples` importance-sampled directions, assuming the view and reflection
+/// directions equal the normal, and reads from lower source mips where the sample density is low
+/// to avoid aliasing.
+pub fn prefilter_ggx_faces(
+    source_mips: &[[Image; 6]],
+    size: u32,
+    roughness: f64,
+    samples: u32,
+) -> [Image; 6] {
+    let alpha = roughness * roughness;
+    let source_size = source_mips[0][0].width as f64;
+    let texel_solid_angle = 4.0 * PI / (6.0 * source_size * source_size);
+
+    Face::ALL.map(|face| {
+        let mut image = Image::new(size, size);
+        for y in 0..size {
+            for x in 0..size {
+                let n = utilities::face_texel_direction(face, x, y, size).normalized();
+                let color = if alpha <= 0.0 {
+                    utilities::sample_faces(&source_mips[0], n, Sampling::Bilinear)
+                } else {
+                    integrate_ggx(source_mips, n, alpha, samples, texel_solid_angle)
+                };
+                image.set(x, y, color);
+            }
+        }
+        image
+    })
+}
+
+fn integrate_ggx(
+    source_mips: &[[Image; 6]],
+    n: TyVector3,
+    alpha: f64,
+    samples: u32,
+    texel_solid_angle: f64,
+) -> [f32; 4] {
+    let up = if n.z.abs() < 0.999 {
+        TyVector3::new(0.0, 0.0, 1.0)
+    } else {
+        TyVector3::new(1.0, 0.0, 0.0)
+    };
+    let tangent_x = up.cross(&n).normalized();
+    let tangent_y = n.cross(&tangent_x);
+    let alpha2 = alpha * alpha;
+    let max_lod = (source_mips.len() - 1) as f64;
+
+    let mut sum = [0.0f64; 4];
+    let mut total_weight = 0.0;
+    for i in 0..samples {
+        let (xi1, xi2) = hammersley(i, samples);
+        let phi = TAU * xi1;
+        let cos_theta = ((1.0 - xi2) / (1.0 + (alpha2 - 1.0) * xi2)).sqrt();
+        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+        let h = tangent_x * (sin_theta * phi.cos())
+            + tangent_y * (sin_theta * phi.sin())
+            + n * cos_theta;
+        let l = h * (2.0 * n.dot(&h)) - n;
+        let n_dot_l = n.dot(&l);
+        if n_dot_l <= 0.0 {
+            continue;
+        }
+
+        // With N = V, the pdf of L is D(H) / 4.
+        let d_denom = cos_theta * cos_theta * (alpha2 - 1.0) + 1.0;
+        let pdf = alpha2 / (PI * d_denom * d_denom) / 4.0;
+        let sample_solid_angle = 1.0 / (samples as f64 * pdf);
+        let lod = (0.5 * (sample_solid_angle / texel_solid_angle).log2() + 1.0).clamp(0.0, max_lod);
+
+        let color =
+            utilities::sample_faces(&source_mips[lod.round() as usize], l, Sampling::Bilinear);
+        for (s, c) in sum.iter_mut().zip(color) {
+            *s += c as f64 * n_dot_l;
+        }
+        total_weight += n_dot_l;
+    }
+
+    sum.map(|s| (s / total_weight) as f32)
+}
+
+/// Returns the `i`th point of an `n`-point Hammersley sequence in `[0, 1)^2`.
+fn hammersley(i: u32, n: u32) -> (f64, f64) {
+    (
+        i as f64 / n as f64,
+        i.reverse_bits() as f64 / (1u64 << 32) as f64,
+    )
+}
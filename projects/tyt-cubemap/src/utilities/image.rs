@@ -0,0 +1,183 @@
+use crate::{Dependencies, Result};
+use std::path::Path;
+use tyt_common::{ImageFormat, linear_to_srgb, srgb_to_linear};
+
+/// An RGBA image with `f32` channels stored row-major.
+#[derive(Clone, Debug, Default)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[f32; 4]>,
+}
+
+impl Image {
+    /// Creates a fully transparent image.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; width as usize * height as usize],
+        }
+    }
+
+    /// Creates an image from RGBA32F pixel data.
+    pub fn from_rgba32f(pixels: &[f32], width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: pixels
+                .chunks_exact(4)
+                .map(|p| [p[0], p[1], p[2], p[3]])
+                .collect(),
+        }
+    }
+
+    /// Returns the image as RGBA32F pixel data.
+    pub fn to_rgba32f(&self) -> Vec<f32> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    /// Converts sRGB-encoded color channels to linear light. Alpha is untouched.
+    pub fn decode_srgb(&mut self) {
+        for pixel in &mut self.pixels {
+            for c in &mut pixel[..3] {
+                *c = srgb_to_linear(*c);
+            }
+        }
+    }
+
+    /// Returns the image as sRGB-encoded RGBA8 pixel data, clamping each channel to `[0, 1]`.
+    pub fn to_srgb8(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|p| {
+                [
+                    linear_to_srgb(p[0]),
+                    linear_to_srgb(p[1]),
+                    linear_to_srgb(p[2]),
+                    p[3],
+                ]
+                .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+            })
+            .collect()
+    }
+
+    /// Returns the pixel at `(x, y)`.
+    pub fn get(&self, x: u32, y: u32) -> [f32; 4] {
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    /// Sets the pixel at `(x, y)`.
+    pub fn set(&mut self, x: u32, y: u32, color: [f32; 4]) {
+        self.pixels[(y * self.width + x) as usize] = color;
+    }
+
+    /// Returns the `width` x `height` region whose top-left corner is `(x, y)`.
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Image {
+        let mut out = Image::new(width, height);
+        for row in 0..height {
+            for col in 0..width {
+                out.set(col, row, self.get(x + col, y + row));
+            }
+        }
+        out
+    }
+
+    /// Copies `src` into this image with its top-left corner at `(x, y)`.
+    pub fn blit(&mut self, src: &Image, x: u32, y: u32) {
+        for row in 0..src.height {
+            for col in 0..src.width {
+                self.set(x + col, y + row, src.get(col, row));
+            }
+        }
+    }
+
+    /// Returns this image at half resolution (rounding up), averaging each 2x2 block.
+    pub fn downsample_box(&self) -> Image {
+        let width = self.width.div_ceil(2).max(1);
+        let height = self.height.div_ceil(2).max(1);
+        let mut out = Image::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0.0; 4];
+                let mut count = 0.0;
+                for sy in (2 * y)..(2 * y + 2).min(self.height) {
+                    for sx in (2 * x)..(2 * x + 2).min(self.width) {
+                        for (s, c) in sum.iter_mut().zip(self.get(sx, sy)) {
+                            *s += c;
+                        }
+                        count += 1.0;
+                    }
+                }
+                out.set(x, y, sum.map(|s| s / count));
+            }
+        }
+        out
+    }
+
+    /// Returns this image rotated clockwise by `quarter_turns` multiples of 90 degrees.
+    pub fn rotate_cw(&self, quarter_turns: u32) -> Image {
+        let (w, h) = (self.width, self.height);
+        match quarter_turns % 4 {
+            0 => self.clone(),
+            1 => {
+                let mut out = Image::new(h, w);
+                for y in 0..w {
+                    for x in 0..h {
+                        out.set(x, y, self.get(y, h - 1 - x));
+                    }
+                }
+                out
+            }
+            2 => {
+                let mut out = Image::new(w, h);
+                for y in 0..h {
+                    for x in 0..w {
+                        out.set(x, y, self.get(w - 1 - x, h - 1 - y));
+                    }
+                }
+                out
+            }
+            _ => {
+                let mut out = Image::new(h, w);
+                for y in 0..w {
+                    for x in 0..h {
+                        out.set(x, y, self.get(w - 1 - y, x));
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+/// Loads an image through `deps`.
+///
+/// Pixels are returned in linear light: LDR formats are decoded from sRGB, while HDR formats are
+/// already linear.
+pub fn load_image(deps: &impl Dependencies, path: impl AsRef<Path>) -> Result<Image> {
+    let path = path.as_ref();
+    let (pixels, width, height) = deps.load_image_rgba32f(path)?;
+    let mut image = Image::from_rgba32f(&pixels, width, height);
+    if !is_hdr_path(path) {
+        image.decode_srgb();
+    }
+    Ok(image)
+}
+
+/// Writes a linear-light image through `deps`.
+///
+/// HDR formats are written at full float precision. LDR formats are sRGB encoded and clamped, so
+/// callers should tonemap HDR content first.
+pub fn write_image(deps: &impl Dependencies, path: impl AsRef<Path>, image: &Image) -> Result<()> {
+    let path = path.as_ref();
+    if is_hdr_path(path) {
+        deps.write_image_rgba32f(path, &image.to_rgba32f(), image.width, image.height)
+    } else {
+        deps.write_image_rgba(path, &image.to_srgb8(), image.width, image.height)
+    }
+}
+
+fn is_hdr_path(path: &Path) -> bool {
+    ImageFormat::from_path(path).is_some_and(ImageFormat::is_hdr)
+}
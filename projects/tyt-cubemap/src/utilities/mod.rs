@@ -1,7 +1,53 @@
+mod coordinate_system;
+mod cube_container;
+mod cube_layout;
+mod direction_transform;
+mod dither;
+mod equirect_to_faces;
+mod face;
+mod face_files;
+mod face_naming;
+mod faces;
 mod faces_to_equirect;
+mod frame_conversion;
+mod ggx;
+mod image;
 mod pixelate_faces;
+mod preview;
+mod procedural_sky;
+mod projection;
+mod reproject;
+mod sampling;
+mod seams;
+mod sky_analysis;
+mod spherical_harmonics;
 mod square;
+mod texel_format;
+mod tonemap;
 
+pub use coordinate_system::*;
+pub use cube_container::*;
+pub use cube_layout::*;
+pub use direction_transform::*;
+pub use dither::*;
+pub use equirect_to_faces::*;
+pub use face::*;
+pub use face_files::*;
+pub use face_naming::*;
+pub use faces::*;
 pub use faces_to_equirect::*;
+pub use frame_conversion::*;
+pub use ggx::*;
+pub use image::*;
 pub use pixelate_faces::*;
+pub use preview::*;
+pub use procedural_sky::*;
+pub use projection::*;
+pub use reproject::*;
+pub use sampling::*;
+pub use seams::*;
+pub use sky_analysis::*;
+pub use spherical_harmonics::*;
 pub use square::*;
+pub use texel_format::*;
+pub use tonemap::*;
@@ -1,25 +1,30 @@
-use crate::{Dependencies, Result};
+use crate::utilities::Image;
 
-const FACES: &[&str] = &["left", "right", "up", "down", "front", "back"];
+/// Point-resizes six cube faces to `size` pixels square, keeping hard pixel edges and the full
+/// float range of HDR input.
+///
+/// Each output pixel takes the source pixel under its center, as ImageMagick's `-filter point`
+/// does.
+pub fn pixelate_faces(faces: &[Image; 6], size: u32) -> [Image; 6] {
+    let size = size.max(1);
+    faces.each_ref().map(|face| {
+        let mut out = Image::new(size, size);
+        if face.width == 0 || face.height == 0 {
+            return out;
+        }
+        for y in 0..size {
+            let sy = source_index(y, size, face.height);
+            for x in 0..size {
+                let sx = source_index(x, size, face.width);
+                out.set(x, y, face.get(sx, sy));
+            }
+        }
+        out
+    })
+}
 
-/// Point-resizes six cube face images to the given height.
-pub fn pixelate_faces(
-    deps: &impl Dependencies,
-    base: &str,
-    out_base: &str,
-    size: u32,
-) -> Result<()> {
-    for face in FACES {
-        let in_path = format!("{base}-{face}.png");
-        let out_path = format!("{out_base}-{face}.png");
-        deps.exec_magick([
-            in_path.as_str(),
-            "-filter",
-            "point",
-            "-resize",
-            &format!("x{size}"),
-            &out_path,
-        ])?;
-    }
-    Ok(())
+/// Returns the source pixel whose span covers the center of output pixel `i`.
+fn source_index(i: u32, out_len: u32, src_len: u32) -> u32 {
+    let center = (i as f64 + 0.5) * src_len as f64 / out_len as f64;
+    (center as u32).min(src_len - 1)
 }
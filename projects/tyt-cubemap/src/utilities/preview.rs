@@ -0,0 +1,47 @@
+use crate::utilities::{DirectionTransform, Image};
+use ty_math::TyVector3;
+
+/// Renders a `width` x `height` pinhole camera view with horizontal field of view `fov_degrees`,
+/// looking in the direction given by `yaw` and `pitch` degrees.
+///
+/// `sample` returns the environment's color in a direction. The camera turns right for positive
+/// yaw and up for positive pitch, matching [`DirectionTransform::from_yaw_pitch_roll`].
+pub fn render_perspective(
+    sample: impl Fn(TyVector3) -> [f32; 4],
+    yaw: f64,
+    pitch: f64,
+    fov_degrees: f64,
+    width: u32,
+    height: u32,
+) -> Image {
+    let orientation = DirectionTransform::from_yaw_pitch_roll(yaw, pitch, 0.0);
+    let half_width = (fov_degrees.to_radians() / 2.0).tan();
+    let half_height = half_width * height as f64 / width as f64;
+    let mut out = Image::new(width, height);
+    for y in 0..height {
+        let up = (1.0 - 2.0 * (y as f64 + 0.5) / height as f64) * half_height;
+        for x in 0..width {
+            let right = (2.0 * (x as f64 + 0.5) / width as f64 - 1.0) * half_width;
+            // Facing +Z with +Y up, the camera's right is -X.
+            let dir = orientation.apply(TyVector3::new(-right, up, 1.0));
+            out.set(x, y, sample(dir));
+        }
+    }
+    out
+}
+
+/// Arranges equally sized `frames` left to right, top to bottom, in a grid that is as close to
+/// square as possible.
+pub fn contact_sheet(frames: &[Image]) -> Image {
+    let cols = (frames.len() as f64).sqrt().ceil().max(1.0) as u32;
+    let rows = (frames.len() as u32).div_ceil(cols).max(1);
+    let (width, height) = frames
+        .first()
+        .map_or((0, 0), |frame| (frame.width, frame.height));
+    let mut out = Image::new(cols * width, rows * height);
+    for (i, frame) in frames.iter().enumerate() {
+        let (col, row) = (i as u32 % cols, i as u32 / cols);
+        out.blit(frame, col * width, row * height);
+    }
+    out
+}
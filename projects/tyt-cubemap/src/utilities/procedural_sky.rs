@@ -0,0 +1,321 @@
+use crate::{
+    CloudsDescription, GradientStop, SkyDescription,
+    utilities::{self, Face, Image},
+};
+use std::f64::consts::{PI, TAU};
+use ty_math::TyVector3;
+
+/// Angular width of the patch of sky a star's brightness is measured over, 0.1 degrees.
+const STAR_WIDTH: f64 = 0.1 * PI / 180.0;
+
+/// Number of sample directions used to place the cloud threshold for the requested coverage.
+const COVERAGE_SAMPLES: u32 = 4096;
+
+/// Sine of the elevation over which clouds fade in above the horizon.
+const CLOUD_FADE_Y: f64 = 0.15;
+
+/// Width of the noise range over which cloud edges blend into clear sky.
+const CLOUD_SOFTNESS: f64 = 0.05;
+
+/// Renders `desc` as a `width` by `height` equirectangular image in linear color.
+pub fn generate_equirect(desc: &SkyDescription, width: u32, height: u32) -> Image {
+    let sky = Sky::new(desc, PI / height as f64);
+    let mut image = Image::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            let dir = utilities::equirect_direction(
+                (x as f64 + 0.5) / width as f64,
+                (y as f64 + 0.5) / height as f64,
+            );
+            image.set(x, y, sky.radiance(dir));
+        }
+    }
+    for (dir, color) in sky.stars() {
+        let (u, v) = utilities::direction_to_equirect(dir);
+        let x = ((u * width as f64) as u32).min(width - 1);
+        let y = ((v * height as f64) as u32).min(height - 1);
+        let solid_angle = utilities::equirect_texel_solid_angle(y, width, height);
+        add_star(&mut image, x, y, color, solid_angle);
+    }
+    image
+}
+
+/// Renders `desc` as six `size` by `size` cube faces, ordered as [`Face::ALL`], in linear color.
+pub fn generate_faces(desc: &SkyDescription, size: u32) -> [Image; 6] {
+    let sky = Sky::new(desc, 0.5 * PI / size as f64);
+    let mut faces = Face::ALL.map(|face| {
+        let mut image = Image::new(size, size);
+        for y in 0..size {
+            for x in 0..size {
+                let dir = utilities::face_texel_direction(face, x, y, size).normalized();
+                image.set(x, y, sky.radiance(dir));
+            }
+        }
+        image
+    });
+    for (dir, color) in sky.stars() {
+        let (face, u, v) = Face::from_direction(dir);
+        let x = (((u + 1.0) * 0.5 * size as f64) as u32).min(size - 1);
+        let y = (((v + 1.0) * 0.5 * size as f64) as u32).min(size - 1);
+        let solid_angle = utilities::face_texel_solid_angle(x, y, size);
+        add_star(&mut faces[face.index()], x, y, color, solid_angle);
+    }
+    faces
+}
+
+/// Adds a star's radiance to the texel it falls in, spread over the texel's solid angle.
+fn add_star(image: &mut Image, x: u32, y: u32, color: [f32; 3], solid_angle: f64) {
+    let scale = (STAR_WIDTH * STAR_WIDTH / solid_angle).min(1.0) as f32;
+    let mut pixel = image.get(x, y);
+    for c in 0..3 {
+        pixel[c] += color[c] * scale;
+    }
+    image.set(x, y, pixel);
+}
+
+/// A sky description prepared for evaluation at a given texel size.
+struct Sky<'a> {
+    desc: &'a SkyDescription,
+    gradient: Vec<GradientStop>,
+    sun: Option<Sun>,
+    clouds: Option<Clouds>,
+}
+
+struct Sun {
+    dir: TyVector3,
+
+    /// Angular radius in radians, widened to at least half a texel so the disc never vanishes.
+    radius: f64,
+
+    /// Angular width in radians over which the disc edge is antialiased.
+    edge: f64,
+
+    /// Radiance of the disc, scaled to keep its energy when the radius is widened.
+    disc: [f32; 3],
+
+    halo: [f32; 3],
+    halo_size: f64,
+}
+
+struct Clouds {
+    desc: CloudsDescription,
+
+    /// Noise value at which clouds begin.
+    threshold: f64,
+}
+
+impl<'a> Sky<'a> {
+    fn new(desc: &'a SkyDescription, texel_angle: f64) -> Self {
+        let mut gradient = desc.gradient.clone();
+        gradient.sort_by(|a, b| a.elevation.total_cmp(&b.elevation));
+        let sun = desc.sun.map(|sun| {
+            let dir = azimuth_elevation_direction(sun.azimuth, sun.elevation);
+            let true_radius = 0.5 * sun.size.to_radians();
+            let radius = true_radius.max(0.5 * texel_angle);
+            let energy = (true_radius / radius).powi(2) as f32;
+            Sun {
+                dir,
+                radius,
+                edge: texel_angle,
+                disc: sun.color.map(|c| c * sun.intensity * energy),
+                halo: sun.color.map(|c| c * sun.halo_intensity),
+                halo_size: sun.halo_size.to_radians().max(f64::EPSILON),
+            }
+        });
+        let clouds = desc.clouds.map(|clouds| Clouds {
+            desc: clouds,
+            threshold: coverage_threshold(&clouds),
+        });
+        Self {
+            desc,
+            gradient,
+            sun,
+            clouds,
+        }
+    }
+
+    /// Returns the radiance of the sky, without stars, in unit direction `dir`.
+    fn radiance(&self, dir: TyVector3) -> [f32; 4] {
+        let elevation = dir.y.clamp(-1.0, 1.0).asin().to_degrees();
+        let mut color = self.gradient_color(elevation);
+        if let Some(sun) = &self.sun {
+            let angle = dir.dot(&sun.dir).clamp(-1.0, 1.0).acos();
+            let coverage = ((sun.radius - angle) / sun.edge + 0.5).clamp(0.0, 1.0) as f32;
+            let halo = (-angle / sun.halo_size).exp() as f32;
+            for (c, value) in color.iter_mut().enumerate() {
+                *value += sun.disc[c] * coverage + sun.halo[c] * halo;
+            }
+        }
+        if let Some(clouds) = &self.clouds {
+            let alpha = clouds.alpha(dir);
+            for (value, cloud) in color.iter_mut().zip(clouds.desc.color) {
+                *value = *value * (1.0 - alpha) + cloud * alpha;
+            }
+        }
+        [color[0], color[1], color[2], 1.0]
+    }
+
+    fn gradient_color(&self, elevation: f64) -> [f32; 3] {
+        let Some(first) = self.gradient.first() else {
+            return [0.0; 3];
+        };
+        if elevation <= first.elevation {
+            return first.color;
+        }
+        for pair in self.gradient.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if elevation <= b.elevation {
+                let span = b.elevation - a.elevation;
+                let t = if span > 0.0 {
+                    ((elevation - a.elevation) / span) as f32
+                } else {
+                    1.0
+                };
+                return [0, 1, 2].map(|c| a.color[c] + (b.color[c] - a.color[c]) * t);
+            }
+        }
+        self.gradient[self.gradient.len() - 1].color
+    }
+
+    /// Returns the unit direction and radiance of each star, dimmed by any clouds in front of it.
+    fn stars(&self) -> Vec<(TyVector3, [f32; 3])> {
+        let Some(stars) = self.desc.stars else {
+            return Vec::new();
+        };
+        let min_y = stars.min_elevation.to_radians().sin();
+        let mut rng = SplitMix64(stars.seed);
+        let mut result = Vec::new();
+        for _ in 0..stars.count {
+            // Draw every value up front so the field doesn't shift when `min_elevation` changes.
+            let y = 2.0 * rng.next_f64() - 1.0;
+            let phi = TAU * rng.next_f64();
+            let brightness = stars.brightness * (rng.next_f64() as f32).powi(2);
+            if y < min_y {
+                continue;
+            }
+            let r = (1.0 - y * y).sqrt();
+            let dir = TyVector3::new(r * phi.cos(), y, r * phi.sin());
+            let visibility = self.clouds.as_ref().map_or(1.0, |c| 1.0 - c.alpha(dir));
+            result.push((dir, [brightness * visibility; 3]));
+        }
+        result
+    }
+}
+
+impl Clouds {
+    /// Returns the cloud opacity in unit direction `dir`.
+    fn alpha(&self, dir: TyVector3) -> f32 {
+        if dir.y <= 0.0 {
+            return 0.0;
+        }
+        let noise = fbm(&self.desc, dir);
+        let density = smoothstep(
+            self.threshold - CLOUD_SOFTNESS,
+            self.threshold + CLOUD_SOFTNESS,
+            noise,
+        );
+        let fade = smoothstep(0.0, CLOUD_FADE_Y, dir.y);
+        (density * fade) as f32 * self.desc.opacity.clamp(0.0, 1.0)
+    }
+}
+
+/// Returns the noise value exceeded over `coverage` of the upper hemisphere, so the requested
+/// fraction of sky ends up cloudy regardless of scale and octaves.
+fn coverage_threshold(clouds: &CloudsDescription) -> f64 {
+    let coverage = (clouds.coverage as f64).clamp(0.0, 1.0);
+    if coverage <= 0.0 {
+        return f64::INFINITY;
+    }
+    // A Fibonacci spiral spreads the samples evenly over the hemisphere.
+    let golden_angle = PI * (3.0 - 5f64.sqrt());
+    let mut values: Vec<f64> = (0..COVERAGE_SAMPLES)
+        .map(|i| {
+            let y = (i as f64 + 0.5) / COVERAGE_SAMPLES as f64;
+            let r = (1.0 - y * y).sqrt();
+            let phi = golden_angle * i as f64;
+            fbm(clouds, TyVector3::new(r * phi.cos(), y, r * phi.sin()))
+        })
+        .collect();
+    values.sort_by(f64::total_cmp);
+    let index = ((1.0 - coverage) * (values.len() - 1) as f64).round() as usize;
+    values[index]
+}
+
+/// Returns the unit direction `azimuth` degrees right of the front and `elevation` degrees up.
+fn azimuth_elevation_direction(azimuth: f64, elevation: f64) -> TyVector3 {
+    let (a, e) = (azimuth.to_radians(), elevation.to_radians());
+    TyVector3::new(-e.cos() * a.sin(), e.sin(), e.cos() * a.cos())
+}
+
+/// Fractal value noise in `[0, 1]` at `dir`, summed over the description's octaves.
+fn fbm(clouds: &CloudsDescription, dir: TyVector3) -> f64 {
+    let mut sum = 0.0;
+    let mut amplitude = 1.0;
+    let mut total = 0.0;
+    let mut frequency = clouds.scale;
+    for octave in 0..clouds.octaves.max(1) {
+        let seed = clouds.seed.wrapping_add(octave as u64);
+        sum += amplitude
+            * value_noise(
+                seed,
+                dir.x * frequency,
+                dir.y * frequency,
+                dir.z * frequency,
+            );
+        total += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    sum / total
+}
+
+/// Smoothly interpolated lattice noise in `[0, 1]`.
+fn value_noise(seed: u64, x: f64, y: f64, z: f64) -> f64 {
+    let (x0, y0, z0) = (x.floor(), y.floor(), z.floor());
+    let (tx, ty, tz) = (
+        smoothstep(0.0, 1.0, x - x0),
+        smoothstep(0.0, 1.0, y - y0),
+        smoothstep(0.0, 1.0, z - z0),
+    );
+    let (ix, iy, iz) = (x0 as i64, y0 as i64, z0 as i64);
+    let corner = |dx: i64, dy: i64, dz: i64| lattice_value(seed, ix + dx, iy + dy, iz + dz);
+    let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+    let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), tx);
+    let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), tx);
+    let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), tx);
+    let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), tx);
+    lerp(lerp(x00, x10, ty), lerp(x01, x11, ty), tz)
+}
+
+/// A pseudorandom value in `[0, 1)` for a lattice point.
+fn lattice_value(seed: u64, x: i64, y: i64, z: i64) -> f64 {
+    let mut rng = SplitMix64(
+        seed ^ (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+            ^ (y as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f)
+            ^ (z as u64).wrapping_mul(0x1656_67b1_9e37_79f9),
+    );
+    rng.next_f64()
+}
+
+fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// A small, fast generator whose output is stable across platforms and releases.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
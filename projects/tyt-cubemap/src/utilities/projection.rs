@@ -0,0 +1,267 @@
+use crate::utilities::{Face, Image, Sampling, equirect_direction, sample_equirect};
+use clap::ValueEnum;
+use std::f64::consts::{FRAC_PI_4, PI};
+use ty_math::TyVector3;
+
+/// A single-image spherical projection other than equirectangular.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Projection {
+    /// Stereographic "little planet" looking straight down, with the front toward the top edge.
+    LittlePlanet,
+
+    /// Equidistant fisheye looking at the front.
+    Fisheye,
+
+    /// A photographed mirror ball: the center reflects the front and the rim reflects the back.
+    MirrorBall,
+
+    /// Angular light-probe map: distance from the center is proportional to the angle from the
+    /// front, reaching the back at the rim.
+    AngularProbe,
+
+    /// Octahedral map with up at the center and the front toward the bottom edge.
+    Octahedral,
+
+    /// Google-style equi-angular cubemap in a 3x2 layout: left, front, and right across the top
+    /// row, then down, back, and up turned to continue along the bottom row.
+    Eac,
+}
+
+/// EAC cells: `(col, row, face, cw_quarter_turns)`.
+///
+/// The top row holds the faces a viewer at `front` sees on their left, ahead, and right.
+const EAC_CELLS: [(u32, u32, Face, u32); 6] = [
+    (0, 0, Face::Right, 0),
+    (1, 0, Face::Front, 0),
+    (2, 0, Face::Left, 0),
+    (0, 1, Face::Down, 3),
+    (1, 1, Face::Back, 1),
+    (2, 1, Face::Up, 3),
+];
+
+impl Projection {
+    /// Returns the width-to-height ratio of images in this projection.
+    pub fn aspect(self) -> f64 {
+        match self {
+            Projection::Eac => 1.5,
+            _ => 1.0,
+        }
+    }
+
+    /// Returns the default field of view in degrees across the image's inscribed circle, for
+    /// projections that have one.
+    pub fn default_fov(self) -> Option<f64> {
+        match self {
+            Projection::LittlePlanet => Some(270.0),
+            Projection::Fisheye => Some(180.0),
+            _ => None,
+        }
+    }
+
+    /// Returns the direction through normalized image coordinates `(u, v)`, each in `[0, 1]`
+    /// with `v` pointing down, or `None` where the projection covers no direction.
+    ///
+    /// `fov` is in radians and only used by projections with a [`Projection::default_fov`].
+    pub fn direction(self, u: f64, v: f64, fov: f64) -> Option<TyVector3> {
+        let (px, py) = (2.0 * u - 1.0, 2.0 * v - 1.0);
+        let r = px.hypot(py);
+        // Unit direction away from the image center, which is zero at the center itself.
+        let (ix, iy) = if r > 0.0 {
+            (px / r, py / r)
+        } else {
+            (0.0, 0.0)
+        };
+        match self {
+            Projection::LittlePlanet => {
+                let theta = 2.0 * (r * (fov / 4.0).tan()).atan();
+                let (s, c) = theta.sin_cos();
+                Some(TyVector3::new(-ix * s, -c, -iy * s))
+            }
+            Projection::Fisheye => {
+                if r > 1.0 {
+                    return None;
+                }
+                let (s, c) = (r * fov / 2.0).sin_cos();
+                Some(TyVector3::new(-ix * s, -iy * s, c))
+            }
+            Projection::MirrorBall => {
+                if r > 1.0 {
+                    return None;
+                }
+                let nz = (1.0 - r * r).sqrt();
+                Some(TyVector3::new(
+                    2.0 * nz * px,
+                    -2.0 * nz * py,
+                    2.0 * nz * nz - 1.0,
+                ))
+            }
+            Projection::AngularProbe => {
+                if r > 1.0 {
+                    return None;
+                }
+                let (s, c) = (r * PI).sin_cos();
+                Some(TyVector3::new(ix * s, -iy * s, c))
+            }
+            Projection::Octahedral => {
+                let y = 1.0 - px.abs() - py.abs();
+                let (a, b) = if y < 0.0 {
+                    ((1.0 - py.abs()) * sign(px), (1.0 - px.abs()) * sign(py))
+                } else {
+                    (px, py)
+                };
+                Some(TyVector3::new(-a, y, b))
+            }
+            Projection::Eac => {
+                let col = ((u * 3.0) as u32).min(2);
+                let row = ((v * 2.0) as u32).min(1);
+                let &(_, _, face, turns) = EAC_CELLS
+                    .iter()
+                    .find(|&&(c, r, _, _)| (c, r) == (col, row))
+                    .expect("every cell holds a face");
+                let cell_u = 2.0 * (u * 3.0 - col as f64) - 1.0;
+                let cell_v = 2.0 * (v * 2.0 - row as f64) - 1.0;
+                let (eu, ev) = rotate_cw(cell_u, cell_v, 4 - turns);
+                Some(face.direction((eu * FRAC_PI_4).tan(), (ev * FRAC_PI_4).tan()))
+            }
+        }
+    }
+
+    /// Returns the normalized image coordinates `(u, v)` that `dir` projects to, or `None` if it
+    /// falls outside the image.
+    ///
+    /// `fov` is in radians and only used by projections with a [`Projection::default_fov`].
+    pub fn coordinates(self, dir: TyVector3, fov: f64) -> Option<(f64, f64)> {
+        let dir = dir.normalized();
+        let to_uv = |px: f64, py: f64| {
+            (px.abs() <= 1.0 && py.abs() <= 1.0).then(|| ((px + 1.0) / 2.0, (py + 1.0) / 2.0))
+        };
+        match self {
+            Projection::LittlePlanet => {
+                let theta = (-dir.y).clamp(-1.0, 1.0).acos();
+                let r = (theta / 2.0).tan() / (fov / 4.0).tan();
+                let h = dir.x.hypot(dir.z);
+                if h == 0.0 {
+                    return to_uv(0.0, 0.0);
+                }
+                to_uv(-dir.x / h * r, -dir.z / h * r)
+            }
+            Projection::Fisheye => {
+                let theta = dir.z.clamp(-1.0, 1.0).acos();
+                if theta > fov / 2.0 {
+                    return None;
+                }
+                let r = theta / (fov / 2.0);
+                let h = dir.x.hypot(dir.y);
+                if h == 0.0 {
+                    return to_uv(0.0, 0.0);
+                }
+                to_uv(-dir.x / h * r, -dir.y / h * r)
+            }
+            Projection::MirrorBall => {
+                // The surface normal is halfway between the camera (+Z) and the reflection.
+                let n = TyVector3::new(dir.x, dir.y, dir.z + 1.0);
+                if n.magnitude() < 1e-9 {
+                    return None;
+                }
+                let n = n.normalized();
+                to_uv(n.x, -n.y)
+            }
+            Projection::AngularProbe => {
+                let r = dir.z.clamp(-1.0, 1.0).acos() / PI;
+                let h = dir.x.hypot(dir.y);
+                if h == 0.0 {
+                    return to_uv(0.0, 0.0);
+                }
+                to_uv(dir.x / h * r, -dir.y / h * r)
+            }
+            Projection::Octahedral => {
+                let l1 = dir.x.abs() + dir.y.abs() + dir.z.abs();
+                let (a, b) = (-dir.x / l1, dir.z / l1);
+                let (a, b) = if dir.y < 0.0 {
+                    ((1.0 - b.abs()) * sign(a), (1.0 - a.abs()) * sign(b))
+                } else {
+                    (a, b)
+                };
+                to_uv(a, b)
+            }
+            Projection::Eac => {
+                let (face, fu, fv) = Face::from_direction(dir);
+                let &(col, row, _, turns) = EAC_CELLS
+                    .iter()
+                    .find(|&&(_, _, f, _)| f == face)
+                    .expect("every face has a cell");
+                let (eu, ev) = (fu.atan() / FRAC_PI_4, fv.atan() / FRAC_PI_4);
+                let (cell_u, cell_v) = rotate_cw(eu, ev, turns);
+                Some((
+                    (col as f64 + (cell_u + 1.0) / 2.0) / 3.0,
+                    (row as f64 + (cell_v + 1.0) / 2.0) / 2.0,
+                ))
+            }
+        }
+    }
+}
+
+/// Reprojects an equirectangular image to a `width` x `height` image in `projection`. Pixels
+/// that cover no direction are left transparent.
+pub fn equirect_to_projection_image(
+    equirect: &Image,
+    projection: Projection,
+    fov: f64,
+    width: u32,
+    height: u32,
+    sampling: Sampling,
+) -> Image {
+    let mut out = Image::new(width, height);
+    for y in 0..height {
+        let v = (y as f64 + 0.5) / height as f64;
+        for x in 0..width {
+            let u = (x as f64 + 0.5) / width as f64;
+            if let Some(dir) = projection.direction(u, v, fov) {
+                out.set(x, y, sample_equirect(equirect, dir, sampling));
+            }
+        }
+    }
+    out
+}
+
+/// Reprojects an image in `projection` to a `width` x `height` equirectangular image. Directions
+/// the projection does not cover are left transparent.
+pub fn projection_to_equirect_image(
+    image: &Image,
+    projection: Projection,
+    fov: f64,
+    width: u32,
+    height: u32,
+    sampling: Sampling,
+) -> Image {
+    let mut out = Image::new(width, height);
+    for y in 0..height {
+        let v = (y as f64 + 0.5) / height as f64;
+        for x in 0..width {
+            let u = (x as f64 + 0.5) / width as f64;
+            let dir = equirect_direction(u, v);
+            if let Some((pu, pv)) = projection.coordinates(dir, fov) {
+                let (mut px, mut py) = (pu * image.width as f64, pv * image.height as f64);
+                if projection == Projection::Eac {
+                    // Keep filtering inside the face's cell so neighboring cells don't bleed in.
+                    let (cell_w, cell_h) = (image.width as f64 / 3.0, image.height as f64 / 2.0);
+                    let (x0, y0) = ((pu * 3.0).floor().min(2.0), (pv * 2.0).floor().min(1.0));
+                    px = px.clamp(x0 * cell_w + 0.5, (x0 + 1.0) * cell_w - 0.5);
+                    py = py.clamp(y0 * cell_h + 0.5, (y0 + 1.0) * cell_h - 0.5);
+                }
+                out.set(x, y, sampling.sample(image, px, py, false));
+            }
+        }
+    }
+    out
+}
+
+/// Rotates normalized cell coordinates (`y` down) clockwise by `quarter_turns` multiples of 90
+/// degrees, matching [`Image::rotate_cw`].
+fn rotate_cw(x: f64, y: f64, quarter_turns: u32) -> (f64, f64) {
+    (0..quarter_turns % 4).fold((x, y), |(x, y), _| (-y, x))
+}
+
+fn sign(value: f64) -> f64 {
+    if value < 0.0 { -1.0 } else { 1.0 }
+}
@@ -0,0 +1,109 @@
+use crate::utilities::{Face, Image, Sampling};
+use std::f64::consts::{PI, TAU};
+use ty_math::TyVector3;
+
+/// Returns the unit direction through normalized equirectangular coordinates `(u, v)`, each in
+/// `[0, 1]` with `(0.5, 0.5)` facing `+Z`.
+pub fn equirect_direction(u: f64, v: f64) -> TyVector3 {
+    let lon = (u - 0.5) * TAU;
+    let lat = (0.5 - v) * PI;
+    TyVector3::new(-lat.cos() * lon.sin(), lat.sin(), lat.cos() * lon.cos())
+}
+
+/// Returns the normalized equirectangular coordinates `(u, v)` that `dir` points at.
+pub fn direction_to_equirect(dir: TyVector3) -> (f64, f64) {
+    let len = dir.magnitude();
+    let lon = (-dir.x).atan2(dir.z);
+    let lat = (dir.y / len).clamp(-1.0, 1.0).asin();
+    (0.5 + lon / TAU, 0.5 - lat / PI)
+}
+
+/// Samples an equirectangular image in direction `dir`.
+pub fn sample_equirect(equirect: &Image, dir: TyVector3, sampling: Sampling) -> [f32; 4] {
+    let (u, v) = direction_to_equirect(dir);
+    sampling.sample(
+        equirect,
+        u * equirect.width as f64,
+        v * equirect.height as f64,
+        true,
+    )
+}
+
+/// Samples six cube faces, ordered as [`Face::ALL`], in direction `dir`.
+pub fn sample_faces(faces: &[Image; 6], dir: TyVector3, sampling: Sampling) -> [f32; 4] {
+    let (face, u, v) = Face::from_direction(dir);
+    let image = &faces[face.index()];
+    sampling.sample(
+        image,
+        (u + 1.0) * 0.5 * image.width as f64,
+        (v + 1.0) * 0.5 * image.height as f64,
+        false,
+    )
+}
+
+/// Returns the direction through the center of texel `(x, y)` of a `size` x `size` face.
+pub fn face_texel_direction(face: Face, x: u32, y: u32, size: u32) -> TyVector3 {
+    let u = 2.0 * (x as f64 + 0.5) / size as f64 - 1.0;
+    let v = 2.0 * (y as f64 + 0.5) / size as f64 - 1.0;
+    face.direction(u, v)
+}
+
+/// Returns the solid angle, in steradians, covered by texel `(x, y)` of a `size` x `size` face.
+pub fn face_texel_solid_angle(x: u32, y: u32, size: u32) -> f64 {
+    fn area(u: f64, v: f64) -> f64 {
+        (u * v).atan2((u * u + v * v + 1.0).sqrt())
+    }
+    let step = 2.0 / size as f64;
+    let u0 = x as f64 * step - 1.0;
+    let v0 = y as f64 * step - 1.0;
+    let (u1, v1) = (u0 + step, v0 + step);
+    area(u0, v0) - area(u0, v1) - area(u1, v0) + area(u1, v1)
+}
+
+/// Returns the solid angle, in steradians, covered by any texel in row `y` of a `width` x
+/// `height` equirectangular image.
+pub fn equirect_texel_solid_angle(y: u32, width: u32, height: u32) -> f64 {
+    let lat0 = (0.5 - y as f64 / height as f64) * PI;
+    let lat1 = (0.5 - (y + 1) as f64 / height as f64) * PI;
+    (lat0.sin() - lat1.sin()) * TAU / width as f64
+}
+
+/// Reprojects an equirectangular image onto a single `size` x `size` cube face.
+pub fn equirect_to_face_image(
+    equirect: &Image,
+    face: Face,
+    size: u32,
+    sampling: Sampling,
+) -> Image {
+    let mut out = Image::new(size, size);
+    for y in 0..size {
+        for x in 0..size {
+            let dir = face_texel_direction(face, x, y, size);
+            out.set(x, y, sample_equirect(equirect, dir, sampling));
+        }
+    }
+    out
+}
+
+/// Reprojects six cube faces, ordered as [`Face::ALL`], onto a `width` x `height`
+/// equirectangular image.
+pub fn faces_to_equirect_image(
+    faces: &[Image; 6],
+    width: u32,
+    height: u32,
+    sampling: Sampling,
+) -> Image {
+    let mut out = Image::new(width, height);
+    for y in 0..height {
+        let v = (y as f64 + 0.5) / height as f64;
+        for x in 0..width {
+            let u = (x as f64 + 0.5) / width as f64;
+            out.set(
+                x,
+                y,
+                sample_faces(faces, equirect_direction(u, v), sampling),
+            );
+        }
+    }
+    out
+}
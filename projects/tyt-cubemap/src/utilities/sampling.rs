@@ -0,0 +1,111 @@
+use crate::utilities::Image;
+use clap::ValueEnum;
+
+/// The filter used when sampling an image at a fractional position.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Sampling {
+    /// Nearest-neighbor sampling, keeping hard pixel edges.
+    Nearest,
+
+    /// Bilinear interpolation over a 2x2 neighborhood.
+    #[default]
+    Bilinear,
+
+    /// Catmull-Rom bicubic interpolation over a 4x4 neighborhood.
+    Bicubic,
+}
+
+impl Sampling {
+    /// Returns the equivalent `interp` option for ffmpeg's `v360` filter.
+    pub fn ffmpeg_interp(self) -> &'static str {
+        match self {
+            Sampling::Nearest => "near",
+            Sampling::Bilinear => "line",
+            Sampling::Bicubic => "cube",
+        }
+    }
+
+    /// Samples `image` at pixel coordinates `(x, y)`, where pixel centers lie at `n + 0.5`.
+    ///
+    /// Columns wrap around when `wrap_x` is set (as for equirectangular images) and are clamped
+    /// otherwise. Rows are always clamped.
+    pub fn sample(self, image: &Image, x: f64, y: f64, wrap_x: bool) -> [f32; 4] {
+        match self {
+            Sampling::Nearest => {
+                let col = x.floor() as i64;
+                let row = y.floor() as i64;
+                texel(image, col, row, wrap_x)
+            }
+            Sampling::Bilinear => {
+                let fx = x - 0.5;
+                let fy = y - 0.5;
+                let col = fx.floor() as i64;
+                let row = fy.floor() as i64;
+                let tx = (fx - col as f64) as f32;
+                let ty = (fy - row as f64) as f32;
+                let top = lerp(
+                    texel(image, col, row, wrap_x),
+                    texel(image, col + 1, row, wrap_x),
+                    tx,
+                );
+                let bottom = lerp(
+                    texel(image, col, row + 1, wrap_x),
+                    texel(image, col + 1, row + 1, wrap_x),
+                    tx,
+                );
+                lerp(top, bottom, ty)
+            }
+            Sampling::Bicubic => {
+                let fx = x - 0.5;
+                let fy = y - 0.5;
+                let col = fx.floor() as i64;
+                let row = fy.floor() as i64;
+                let wx = catmull_rom_weights((fx - col as f64) as f32);
+                let wy = catmull_rom_weights((fy - row as f64) as f32);
+                let mut out = [0.0; 4];
+                for (j, wy) in wy.iter().enumerate() {
+                    for (i, wx) in wx.iter().enumerate() {
+                        let c = texel(image, col + i as i64 - 1, row + j as i64 - 1, wrap_x);
+                        let w = wx * wy;
+                        for k in 0..4 {
+                            out[k] += c[k] * w;
+                        }
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+fn texel(image: &Image, col: i64, row: i64, wrap_x: bool) -> [f32; 4] {
+    let w = image.width as i64;
+    let h = image.height as i64;
+    let col = if wrap_x {
+        col.rem_euclid(w)
+    } else {
+        col.clamp(0, w - 1)
+    };
+    let row = row.clamp(0, h - 1);
+    image.get(col as u32, row as u32)
+}
+
+fn lerp(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
+
+fn catmull_rom_weights(t: f32) -> [f32; 4] {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    [
+        0.5 * (-t3 + 2.0 * t2 - t),
+        0.5 * (3.0 * t3 - 5.0 * t2 + 2.0),
+        0.5 * (-3.0 * t3 + 4.0 * t2 + t),
+        0.5 * (t3 - t2),
+    ]
+}
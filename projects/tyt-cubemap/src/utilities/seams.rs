@@ -0,0 +1,237 @@
+use crate::utilities::{Face, Image};
+use clap::ValueEnum;
+
+/// How [`fix_seams`] makes the texels on either side of a seam agree.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum SeamFix {
+    /// Replace both sides with their average.
+    #[default]
+    Blend,
+
+    /// Copy the first face's border onto its neighbors, keeping exact colors (and palettes).
+    /// Only the border texels change.
+    Copy,
+}
+
+/// A border texel along with the step that moves one texel into the face.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SeamTexel {
+    pub face: Face,
+    pub x: u32,
+    pub y: u32,
+    pub inward: (i32, i32),
+}
+
+/// One of the 12 cube edges, as pairs of border texels that touch across it.
+#[derive(Clone, Debug)]
+pub struct Seam {
+    pub faces: (Face, Face),
+    pub texels: Vec<(SeamTexel, SeamTexel)>,
+}
+
+/// Color discontinuity along a seam, measured in linear light as the largest per-channel
+/// difference between texels.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SeamScore {
+    /// Mean difference across the seam.
+    pub mean: f32,
+
+    /// Largest difference across the seam.
+    pub max: f32,
+
+    /// Mean difference between each border texel and the texel inward from it, for comparison.
+    pub interior: f32,
+}
+
+/// Returns the 12 seams of a cube with `size` x `size` faces.
+///
+/// Neighbors are found by stepping just past each face edge and asking which face that direction
+/// falls on, so the pairing follows each face's orientation.
+pub fn cube_seams(size: u32) -> Vec<Seam> {
+    const PAST_EDGE: f64 = 1.0 + 1e-6;
+    const ON_EDGE: f64 = 1.0 - 1e-3;
+
+    let mut seams: Vec<Seam> = Vec::new();
+    for face in Face::ALL {
+        for i in 0..size {
+            let t = 2.0 * (i as f64 + 0.5) / size as f64 - 1.0;
+            // (texel, inward step, face coordinates just past the edge) for each edge.
+            let edges = [
+                ((i, 0), (0, 1), (t, -PAST_EDGE)),
+                ((size - 1, i), (-1, 0), (PAST_EDGE, t)),
+                ((i, size - 1), (0, -1), (t, PAST_EDGE)),
+                ((0, i), (1, 0), (-PAST_EDGE, t)),
+            ];
+            for ((x, y), inward, (u, v)) in edges {
+                let (neighbor, nu, nv) = Face::from_direction(face.direction(u, v));
+                if neighbor.index() <= face.index() {
+                    continue;
+                }
+                let to_texel = |c: f64| (((c + 1.0) * 0.5 * size as f64) as u32).min(size - 1);
+                let neighbor_inward = if nu > ON_EDGE {
+                    (-1, 0)
+                } else if nu < -ON_EDGE {
+                    (1, 0)
+                } else if nv > ON_EDGE {
+                    (0, -1)
+                } else {
+                    (0, 1)
+                };
+                let pair = (
+                    SeamTexel { face, x, y, inward },
+                    SeamTexel {
+                        face: neighbor,
+                        x: to_texel(nu),
+                        y: to_texel(nv),
+                        inward: neighbor_inward,
+                    },
+                );
+                match seams.iter_mut().find(|s| s.faces == (face, neighbor)) {
+                    Some(seam) => seam.texels.push(pair),
+                    None => seams.push(Seam {
+                        faces: (face, neighbor),
+                        texels: vec![pair],
+                    }),
+                }
+            }
+        }
+    }
+    seams
+}
+
+/// Scores how visible `seam` is on `faces`, which are ordered as [`Face::ALL`].
+pub fn score_seam(faces: &[Image; 6], seam: &Seam) -> SeamScore {
+    let mut score = SeamScore::default();
+    for (a, b) in &seam.texels {
+        let across = difference(texel(faces, a, 0), texel(faces, b, 0));
+        score.mean += across;
+        score.max = score.max.max(across);
+        score.interior += 0.5
+            * (difference(texel(faces, a, 0), texel(faces, a, 1))
+                + difference(texel(faces, b, 0), texel(faces, b, 1)));
+    }
+    let count = seam.texels.len().max(1) as f32;
+    score.mean /= count;
+    score.interior /= count;
+    score
+}
+
+/// Makes the two sides of every seam agree, feathering the correction `width` texels into each
+/// face.
+///
+/// Texels that touch across seams are resolved together, so the corner texels shared by three
+/// faces settle on one color instead of being shifted again by each seam. With
+/// [`SeamFix::Blend`], texels inside the feather are shifted by a fading fraction of the border's
+/// correction, which removes the step while keeping their detail. [`SeamFix::Copy`] rewrites
+/// only the border texels, ignoring `width`.
+pub fn fix_seams(faces: &mut [Image; 6], seams: &[Seam], fix: SeamFix, width: u32) {
+    let size = faces[0].width;
+    let texels = (size * size) as usize;
+    let at = |face: Face, x: u32, y: u32| face.index() * texels + (y * size + x) as usize;
+    let borders: Vec<&SeamTexel> = seams
+        .iter()
+        .flat_map(|seam| seam.texels.iter().flat_map(|(a, b)| [a, b]))
+        .collect();
+
+    // Group texels that touch across a seam, rooted at the lowest index, which is on the group's
+    // first face.
+    let mut parent: Vec<usize> = (0..6 * texels).collect();
+    for seam in seams {
+        for (a, b) in &seam.texels {
+            let ra = group_root(&mut parent, at(a.face, a.x, a.y));
+            let rb = group_root(&mut parent, at(b.face, b.x, b.y));
+            parent[ra.max(rb)] = ra.min(rb);
+        }
+    }
+
+    // Corner texels lie on two seams of their face, so count each texel once.
+    let mut counted = vec![false; 6 * texels];
+    let mut sums = vec![[0.0f32; 4]; 6 * texels];
+    let mut counts = vec![0.0f32; 6 * texels];
+    for border in &borders {
+        let i = at(border.face, border.x, border.y);
+        if std::mem::replace(&mut counted[i], true) {
+            continue;
+        }
+        let root = group_root(&mut parent, i);
+        if fix == SeamFix::Blend || i == root {
+            let color = texel(faces, border, 0);
+            for c in 0..4 {
+                sums[root][c] += color[c];
+            }
+            counts[root] += 1.0;
+        }
+    }
+
+    // Border texels take their group's color exactly. Their corrections spread inward, gathered
+    // as (weighted sum, total weight, largest weight).
+    let mut corrections: Vec<Option<[f32; 4]>> = vec![None; 6 * texels];
+    let mut shifts = vec![([0.0f32; 4], 0.0f32, 0.0f32); 6 * texels];
+    let depths = match fix {
+        SeamFix::Blend => width.min(size / 2).max(1),
+        SeamFix::Copy => 1,
+    };
+    for border in &borders {
+        let i = at(border.face, border.x, border.y);
+        let root = group_root(&mut parent, i);
+        let color = texel(faces, border, 0);
+        let correction = [0, 1, 2, 3].map(|c| sums[root][c] / counts[root] - color[c]);
+        corrections[i] = Some(correction);
+        for depth in 1..depths {
+            let weight = 1.0 - depth as f32 / depths as f32;
+            let (x, y) = step(border, depth);
+            let (sum, total, largest) = &mut shifts[at(border.face, x, y)];
+            for c in 0..4 {
+                sum[c] += weight * correction[c];
+            }
+            *total += weight;
+            *largest = largest.max(weight);
+        }
+    }
+
+    for face in Face::ALL {
+        for y in 0..size {
+            for x in 0..size {
+                let i = at(face, x, y);
+                let (sum, total, largest) = shifts[i];
+                // Inside the feather, the corrections of nearby borders are mixed.
+                let shift = match corrections[i] {
+                    Some(correction) => correction,
+                    None if total > 0.0 => sum.map(|c| c / total * largest),
+                    None => continue,
+                };
+                let color = faces[face.index()].get(x, y);
+                faces[face.index()].set(x, y, [0, 1, 2, 3].map(|c| color[c] + shift[c]));
+            }
+        }
+    }
+}
+
+/// Returns the root of `i`'s group, flattening the path to it.
+fn group_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+fn step(texel: &SeamTexel, depth: u32) -> (u32, u32) {
+    (
+        texel.x.saturating_add_signed(texel.inward.0 * depth as i32),
+        texel.y.saturating_add_signed(texel.inward.1 * depth as i32),
+    )
+}
+
+fn texel(faces: &[Image; 6], texel: &SeamTexel, depth: u32) -> [f32; 4] {
+    let image = &faces[texel.face.index()];
+    let (x, y) = step(texel, depth);
+    image.get(x.min(image.width - 1), y.min(image.height - 1))
+}
+
+fn difference(a: [f32; 4], b: [f32; 4]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f32::max)
+}
@@ -0,0 +1,222 @@
+use crate::utilities::{self, Face, Image};
+use ty_math::{TyRgbaColor, TyVector3};
+
+/// The brightest direction and representative colors of a sky, in linear color and the
+/// cubemap's right-handed, Y-up frame.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SkyAnalysis {
+    /// Unit direction toward the sun.
+    pub sun_direction: TyVector3,
+
+    /// Luminance integrated over the sun's solid angle, which is the illuminance it casts on a
+    /// surface facing it.
+    pub sun_intensity: f64,
+
+    /// Mean radiance across the sun.
+    pub sun_color: TyRgbaColor,
+
+    /// Mean radiance at least 60 degrees above the horizon.
+    pub zenith: TyRgbaColor,
+
+    /// Mean radiance up to 15 degrees above the horizon.
+    pub horizon: TyRgbaColor,
+
+    /// Mean radiance below the horizon.
+    pub ground: TyRgbaColor,
+
+    /// Mean radiance over the whole sphere.
+    pub ambient: TyRgbaColor,
+}
+
+/// Side length, in bins per cube face, of the grid used to find the brightest region.
+const PEAK_BINS: u32 = 8;
+
+/// Angular radius around the brightest texel within which sun texels are gathered.
+const SUN_CONE_DEGREES: f64 = 15.0;
+
+/// Fraction of the brightest luminance in the sun cone that a texel needs to count as sun.
+const SUN_THRESHOLD: f64 = 0.5;
+
+/// Sine of the lowest zenith elevation, 60 degrees.
+const ZENITH_MIN_Y: f64 = 0.866_025;
+
+/// Sine of the highest horizon elevation, 15 degrees.
+const HORIZON_MAX_Y: f64 = 0.258_819;
+
+/// Analyzes six cube faces, ordered as [`Face::ALL`].
+pub fn analyze_faces(faces: &[Image; 6]) -> SkyAnalysis {
+    let mut texels = Vec::new();
+    for face in Face::ALL {
+        let image = &faces[face.index()];
+        let size = image.width;
+        for y in 0..size {
+            for x in 0..size {
+                texels.push(Texel {
+                    dir: utilities::face_texel_direction(face, x, y, size).normalized(),
+                    solid_angle: utilities::face_texel_solid_angle(x, y, size),
+                    color: image.get(x, y),
+                });
+            }
+        }
+    }
+    analyze(&texels)
+}
+
+/// Analyzes an equirectangular image.
+pub fn analyze_equirect(equirect: &Image) -> SkyAnalysis {
+    let (width, height) = (equirect.width, equirect.height);
+    let mut texels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        let solid_angle = utilities::equirect_texel_solid_angle(y, width, height);
+        let v = (y as f64 + 0.5) / height as f64;
+        for x in 0..width {
+            let u = (x as f64 + 0.5) / width as f64;
+            texels.push(Texel {
+                dir: utilities::equirect_direction(u, v).normalized(),
+                solid_angle,
+                color: equirect.get(x, y),
+            });
+        }
+    }
+    analyze(&texels)
+}
+
+struct Texel {
+    dir: TyVector3,
+    solid_angle: f64,
+    color: [f32; 4],
+}
+
+impl Texel {
+    fn luminance(&self) -> f64 {
+        let [r, g, b, _] = self.color;
+        0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64
+    }
+}
+
+fn analyze(texels: &[Texel]) -> SkyAnalysis {
+    let is_sun = find_sun(texels);
+
+    let mut sun = ColorMean::default();
+    let mut sun_direction = TyVector3::new(0.0, 0.0, 0.0);
+    let mut sun_intensity = 0.0;
+    let (mut zenith, mut horizon, mut ground, mut ambient) = (
+        ColorMean::default(),
+        ColorMean::default(),
+        ColorMean::default(),
+        ColorMean::default(),
+    );
+    for (texel, &is_sun) in texels.iter().zip(&is_sun) {
+        if is_sun {
+            let energy = texel.luminance() * texel.solid_angle;
+            sun_direction = sun_direction + texel.dir * energy;
+            sun_intensity += energy;
+            sun.add(texel);
+            continue;
+        }
+        let y = texel.dir.y;
+        let band: Option<&mut ColorMean> = if y >= ZENITH_MIN_Y {
+            Some(&mut zenith)
+        } else if (0.0..HORIZON_MAX_Y).contains(&y) {
+            Some(&mut horizon)
+        } else if y < 0.0 {
+            Some(&mut ground)
+        } else {
+            None
+        };
+        if let Some(band) = band {
+            band.add(texel);
+        }
+        ambient.add(texel);
+    }
+
+    SkyAnalysis {
+        sun_direction: if sun_direction.magnitude() > 0.0 {
+            sun_direction.normalized()
+        } else {
+            TyVector3::new(0.0, 1.0, 0.0)
+        },
+        sun_intensity,
+        sun_color: sun.finish(),
+        zenith: zenith.finish(),
+        horizon: horizon.finish(),
+        ground: ground.finish(),
+        ambient: ambient.finish(),
+    }
+}
+
+/// Flags the texels that make up the sun.
+///
+/// Texels are first gathered into bins of roughly equal solid angle, so that a region's
+/// brightness is its mean radiance rather than depending on how densely the input samples it.
+/// The sun is then the texels near the brightest texel of the brightest bin that are at least
+/// [`SUN_THRESHOLD`] as bright as the brightest texel nearby.
+fn find_sun(texels: &[Texel]) -> Vec<bool> {
+    let bin_count = (6 * PEAK_BINS * PEAK_BINS) as usize;
+    let mut bins = vec![(0.0, 0.0); bin_count];
+    let bin_of = |dir: TyVector3| {
+        let (face, u, v) = Face::from_direction(dir);
+        let to_bin = |c: f64| (((c + 1.0) * 0.5 * PEAK_BINS as f64) as u32).min(PEAK_BINS - 1);
+        (face.index() as u32 * PEAK_BINS * PEAK_BINS + to_bin(v) * PEAK_BINS + to_bin(u)) as usize
+    };
+    for texel in texels {
+        let bin = &mut bins[bin_of(texel.dir)];
+        bin.0 += texel.luminance() * texel.solid_angle;
+        bin.1 += texel.solid_angle;
+    }
+    let mean = |(energy, solid_angle): (f64, f64)| {
+        if solid_angle > 0.0 {
+            energy / solid_angle
+        } else {
+            0.0
+        }
+    };
+    let Some(peak_bin) = (0..bin_count).max_by(|&a, &b| mean(bins[a]).total_cmp(&mean(bins[b])))
+    else {
+        return vec![false; texels.len()];
+    };
+    let Some(peak) = texels
+        .iter()
+        .filter(|texel| bin_of(texel.dir) == peak_bin)
+        .max_by(|a, b| a.luminance().total_cmp(&b.luminance()))
+    else {
+        return vec![false; texels.len()];
+    };
+
+    let cone_cos = SUN_CONE_DEGREES.to_radians().cos();
+    let in_cone = |texel: &Texel| texel.dir.dot(&peak.dir) >= cone_cos;
+    let threshold = SUN_THRESHOLD
+        * texels
+            .iter()
+            .filter(|texel| in_cone(texel))
+            .map(Texel::luminance)
+            .fold(0.0, f64::max);
+    texels
+        .iter()
+        .map(|texel| in_cone(texel) && texel.luminance() >= threshold && threshold > 0.0)
+        .collect()
+}
+
+/// A solid-angle-weighted mean color.
+#[derive(Default)]
+struct ColorMean {
+    sum: [f64; 3],
+    solid_angle: f64,
+}
+
+impl ColorMean {
+    fn add(&mut self, texel: &Texel) {
+        for (sum, channel) in self.sum.iter_mut().zip(texel.color) {
+            *sum += channel as f64 * texel.solid_angle;
+        }
+        self.solid_angle += texel.solid_angle;
+    }
+
+    fn finish(self) -> TyRgbaColor {
+        if self.solid_angle <= 0.0 {
+            return TyRgbaColor::new(0.0, 0.0, 0.0, 1.0);
+        }
+        let [r, g, b] = self.sum.map(|sum| (sum / self.solid_angle) as f32);
+        TyRgbaColor::new(r, g, b, 1.0)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    Result,
+    utilities::{self, Face, Image},
+};
+use std::f64::consts::PI;
+use ty_math::TyVector3;
+use tyt_common::invalid_data;
+
+/// RGB coefficients of an L2 (nine coefficient) real spherical harmonic expansion, ordered by
+/// band and then by `m`: `(0, 0)`, `(1, -1)`, `(1, 0)`, `(1, 1)`, `(2, -2)`, `(2, -1)`, `(2, 0)`,
+/// `(2, 1)`, `(2, 2)`.
+pub type Sh9 = [[f64; 3]; 9];
+
+/// Evaluates the nine L2 real spherical harmonic basis functions in unit direction `dir`.
+pub fn sh9_basis(dir: TyVector3) -> [f64; 9] {
+    let TyVector3 { x, y, z } = dir;
+    [
+        0.282_095,
+        0.488_603 * y,
+        0.488_603 * z,
+        0.488_603 * x,
+        1.092_548 * x * y,
+        1.092_548 * y * z,
+        0.315_392 * (3.0 * z * z - 1.0),
+        1.092_548 * x * z,
+        0.546_274 * (x * x - y * y),
+    ]
+}
+
+/// Projects six cube faces, ordered as [`Face::ALL`], onto L2 spherical harmonics, weighting each
+/// texel by the solid angle it covers. Fails if the faces are empty.
+pub fn project_faces_sh9(faces: &[Image; 6]) -> Result<Sh9> {
+    let mut acc = ShAccumulator::default();
+    for face in Face::ALL {
+        let image = &faces[face.index()];
+        let size = image.width;
+        for y in 0..size {
+            for x in 0..size {
+                acc.add(
+                    utilities::face_texel_direction(face, x, y, size),
+                    utilities::face_texel_solid_angle(x, y, size),
+                    image.get(x, y),
+                );
+            }
+        }
+    }
+    acc.finish()
+}
+
+/// Projects an equirectangular image onto L2 spherical harmonics, weighting each texel by the
+/// solid angle it covers. Fails if the image is empty.
+pub fn project_equirect_sh9(equirect: &Image) -> Result<Sh9> {
+    let (width, height) = (equirect.width, equirect.height);
+    let mut acc = ShAccumulator::default();
+    for y in 0..height {
+        let weight = utilities::equirect_texel_solid_angle(y, width, height);
+        let v = (y as f64 + 0.5) / height as f64;
+        for x in 0..width {
+            let u = (x as f64 + 0.5) / width as f64;
+            acc.add(
+                utilities::equirect_direction(u, v),
+                weight,
+                equirect.get(x, y),
+            );
+        }
+    }
+    acc.finish()
+}
+
+/// Evaluates the diffuse irradiance encoded by `sh` for surface normal `normal`, divided by π so
+/// that a uniform environment of radiance `L` yields `L`.
+pub fn sh9_irradiance(sh: &Sh9, normal: TyVector3) -> [f64; 3] {
+    // Clamped-cosine convolution weights per band, pre-divided by π.
+    const BAND_WEIGHTS: [f64; 9] = [
+        1.0,
+        2.0 / 3.0,
+        2.0 / 3.0,
+        2.0 / 3.0,
+        0.25,
+        0.25,
+        0.25,
+        0.25,
+        0.25,
+    ];
+    let basis = sh9_basis(normal.normalized());
+    let mut out = [0.0; 3];
+    for ((coefficient, weight), b) in sh.iter().zip(BAND_WEIGHTS).zip(basis) {
+        for (o, c) in out.iter_mut().zip(coefficient) {
+            *o += c * weight * b;
+        }
+    }
+    out
+}
+
+/// Renders the irradiance encoded by `sh` onto six `size` x `size` faces, ordered as
+/// [`Face::ALL`].
+pub fn sh9_irradiance_faces(sh: &Sh9, size: u32) -> [Image; 6] {
+    Face::ALL.map(|face| {
+        let mut image = Image::new(size, size);
+        for y in 0..size {
+            for x in 0..size {
+                let [r, g, b] =
+                    sh9_irradiance(sh, utilities::face_texel_direction(face, x, y, size));
+                image.set(x, y, [r as f32, g as f32, b as f32, 1.0]);
+            }
+        }
+        image
+    })
+}
+
+#[derive(Default)]
+struct ShAccumulator {
+    sh: Sh9,
+    total_weight: f64,
+}
+
+impl ShAccumulator {
+    fn add(&mut self, dir: TyVector3, weight: f64, color: [f32; 4]) {
+        let basis = sh9_basis(dir.normalized());
+        for (coefficient, b) in self.sh.iter_mut().zip(basis) {
+            for (c, channel) in coefficient.iter_mut().zip(color) {
+                *c += channel as f64 * b * weight;
+            }
+        }
+        self.total_weight += weight;
+    }
+
+    /// Rescales the sums so the weights cover exactly the full sphere, failing if nothing was
+    /// added.
+    fn finish(mut self) -> Result<Sh9> {
+        if self.total_weight <= 0.0 {
+            return Err(invalid_data("the input image is empty"));
+        }
+        let scale = 4.0 * PI / self.total_weight;
+        for coefficient in &mut self.sh {
+            for c in coefficient {
+                *c *= scale;
+            }
+        }
+        Ok(self.sh)
+    }
+}
@@ -1,16 +1,9 @@
-use crate::{Dependencies, Result};
+use crate::utilities::Image;
 
-/// Pads an image to a square canvas with transparent background.
-pub fn square(deps: &impl Dependencies, in_path: &str, out_path: &str) -> Result<()> {
-    deps.exec_magick([
-        in_path,
-        "-background",
-        "none",
-        "-gravity",
-        "center",
-        "-extent",
-        "%[fx:max(w,h)]x%[fx:max(w,h)]",
-        out_path,
-    ])?;
-    Ok(())
+/// Centers an image on a transparent square canvas whose side is its larger dimension.
+pub fn square_image(image: &Image) -> Image {
+    let side = image.width.max(image.height);
+    let mut out = Image::new(side, side);
+    out.blit(image, (side - image.width) / 2, (side - image.height) / 2);
+    out
 }
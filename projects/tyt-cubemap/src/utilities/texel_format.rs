@@ -0,0 +1,143 @@
+use crate::utilities::Image;
+use clap::ValueEnum;
+use tyt_common::TextureFormat;
+
+/// The uncompressed texel format used inside KTX2 and DDS containers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum TexelFormat {
+    /// 8 bits per channel, sRGB-encoded color with linear alpha.
+    Rgba8,
+
+    /// 16-bit half-float per channel, linear.
+    Rgba16f,
+
+    /// 32-bit float per channel, linear.
+    Rgba32f,
+}
+
+impl TexelFormat {
+    /// Returns `true` for floating-point formats, which hold linear HDR values.
+    pub fn is_float(self) -> bool {
+        self != TexelFormat::Rgba8
+    }
+
+    /// Returns the container texel format this format is stored as.
+    pub fn texture_format(self) -> TextureFormat {
+        match self {
+            TexelFormat::Rgba8 => TextureFormat::Rgba8Srgb,
+            TexelFormat::Rgba16f => TextureFormat::Rgba16Float,
+            TexelFormat::Rgba32f => TextureFormat::Rgba32Float,
+        }
+    }
+
+    /// Returns the format that holds texels stored as `format`, and whether its 8-bit color
+    /// channels are sRGB-encoded.
+    pub fn from_texture_format(format: TextureFormat) -> (TexelFormat, bool) {
+        match format {
+            TextureFormat::Rgba8Unorm => (TexelFormat::Rgba8, false),
+            TextureFormat::Rgba8Srgb => (TexelFormat::Rgba8, true),
+            TextureFormat::Rgba16Float => (TexelFormat::Rgba16f, false),
+            TextureFormat::Rgba32Float => (TexelFormat::Rgba32f, false),
+        }
+    }
+
+    /// Encodes a linear-light image as tightly packed, top-to-bottom rows of texels.
+    pub fn encode(self, image: &Image) -> Vec<u8> {
+        match self {
+            TexelFormat::Rgba8 => image.to_srgb8(),
+            TexelFormat::Rgba16f => image
+                .pixels
+                .iter()
+                .flatten()
+                .flat_map(|&c| f32_to_f16_bits(c).to_le_bytes())
+                .collect(),
+            TexelFormat::Rgba32f => image
+                .pixels
+                .iter()
+                .flatten()
+                .flat_map(|&c| c.to_le_bytes())
+                .collect(),
+        }
+    }
+
+    /// Decodes tightly packed texels into a linear-light image. `srgb` selects whether 8-bit color
+    /// channels are sRGB-encoded.
+    pub fn decode(self, bytes: &[u8], width: u32, height: u32, srgb: bool) -> Image {
+        let channels: Vec<f32> = match self {
+            TexelFormat::Rgba8 => bytes.iter().map(|&b| b as f32 / 255.0).collect(),
+            TexelFormat::Rgba16f => bytes
+                .chunks_exact(2)
+                .map(|b| f16_bits_to_f32(u16::from_le_bytes([b[0], b[1]])))
+                .collect(),
+            TexelFormat::Rgba32f => bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+        };
+        let mut image = Image::from_rgba32f(&channels, width, height);
+        if self == TexelFormat::Rgba8 && srgb {
+            image.decode_srgb();
+        }
+        image
+    }
+}
+
+/// Converts an `f32` to IEEE 754 half-precision bits, rounding to nearest even.
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        // Infinity or NaN.
+        return sign | 0x7c00 | if mantissa != 0 { 0x200 } else { 0 };
+    }
+
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if half_exponent <= 0 {
+        if half_exponent < -10 {
+            return sign;
+        }
+        // Subnormal half.
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - half_exponent) as u32;
+        let half_mantissa = mantissa >> shift;
+        let remainder = mantissa & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        let round_up = remainder > halfway || (remainder == halfway && half_mantissa & 1 == 1);
+        return sign | (half_mantissa + round_up as u32) as u16;
+    }
+
+    let half = ((half_exponent as u32) << 10) | (mantissa >> 13);
+    let remainder = mantissa & 0x1fff;
+    let round_up = remainder > 0x1000 || (remainder == 0x1000 && half & 1 == 1);
+    // A carry out of the mantissa correctly bumps the exponent (and saturates to infinity).
+    sign | (half + round_up as u32) as u16
+}
+
+/// Converts IEEE 754 half-precision bits to an `f32`.
+fn f16_bits_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+    let out = match (exponent, mantissa) {
+        (0, 0) => sign,
+        (0, _) => {
+            // Subnormal half: normalize into an f32.
+            let mut e = 127 - 15 + 1;
+            let mut m = mantissa;
+            while m & 0x400 == 0 {
+                m <<= 1;
+                e -= 1;
+            }
+            sign | (e << 23) | ((m & 0x3ff) << 13)
+        }
+        (0x1f, _) => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(out)
+}
@@ -0,0 +1,34 @@
+use crate::utilities::Image;
+use clap::ValueEnum;
+
+/// The operator used to compress HDR color into `[0, 1]` for LDR output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Tonemap {
+    /// Clamp each channel to `[0, 1]`.
+    #[default]
+    None,
+
+    /// Reinhard's `c / (1 + c)` operator.
+    Reinhard,
+
+    /// Narkowicz's fit of the ACES filmic curve.
+    Aces,
+}
+
+impl Tonemap {
+    /// Scales `image`'s color by `2^exposure` and then applies this operator. Alpha is untouched.
+    pub fn apply(self, image: &mut Image, exposure: f32) {
+        let scale = exposure.exp2();
+        for pixel in &mut image.pixels {
+            for c in &mut pixel[..3] {
+                let x = *c * scale;
+                *c = match self {
+                    Tonemap::None => x,
+                    Tonemap::Reinhard => x / (1.0 + x),
+                    Tonemap::Aces => (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14),
+                }
+                .clamp(0.0, 1.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+/// The JSON sidecar describing packed atlases, written by `pack-atlas` and read by `slice`.
+/// Positions and sizes are in pixels, with the origin at the top left.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize, serde::Serialize))]
+pub struct AtlasMetadata {
+    pub pages: Vec<AtlasPage>,
+
+    pub sprites: Vec<AtlasSprite>,
+
+    /// The cell layout when the atlas is a uniform grid flipbook.
+    #[cfg_attr(
+        feature = "impl",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub grid: Option<AtlasGrid>,
+}
+
+/// One atlas image.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize, serde::Serialize))]
+pub struct AtlasPage {
+    /// The image file name, relative to the sidecar.
+    pub file: String,
+
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Where one sprite was packed.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize, serde::Serialize))]
+pub struct AtlasSprite {
+    pub name: String,
+
+    /// Index into the pages.
+    pub page: usize,
+
+    /// The sprite's pixels within the page, excluding padding and extrusion. When `rotated`, the
+    /// sprite is stored turned a quarter turn clockwise, so `width` and `height` are swapped
+    /// relative to it.
+    pub rect: AtlasRect,
+
+    #[cfg_attr(feature = "impl", serde(default))]
+    pub rotated: bool,
+
+    /// Position of the kept pixels within the original image, nonzero when transparent borders
+    /// were trimmed.
+    #[cfg_attr(feature = "impl", serde(default))]
+    pub offset: AtlasPoint,
+
+    /// Size of the original image before trimming.
+    pub source_size: AtlasSize,
+}
+
+/// The cell layout of a grid flipbook, with frames in reading order.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize, serde::Serialize))]
+pub struct AtlasGrid {
+    /// Cells across and down each page.
+    pub columns: u32,
+    pub rows: u32,
+
+    pub cell_width: u32,
+    pub cell_height: u32,
+
+    /// Gap between the page edge and the first cell.
+    pub margin: u32,
+
+    /// Gap between neighboring cells.
+    pub spacing: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize, serde::Serialize))]
+pub struct AtlasRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize, serde::Serialize))]
+pub struct AtlasPoint {
+    pub x: u32,
+    pub y: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "impl", derive(serde::Deserialize, serde::Serialize))]
+pub struct AtlasSize {
+    pub width: u32,
+    pub height: u32,
+}
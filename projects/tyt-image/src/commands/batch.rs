@@ -0,0 +1,111 @@
+use crate::{Dependencies, Error, Result, TytImage};
+use clap::{CommandFactory, Parser};
+use std::{io::Error as IOError, path::Path};
+use tyt_common::{ImageFormat, invalid_data};
+
+/// Runs another image command once for every file matched by a glob or inside a directory.
+///
+/// Each file is passed as the command's input. A file that fails is reported in the summary at
+/// the end without stopping the others.
+#[derive(Clone, Debug, Parser)]
+pub struct Batch {
+    /// Glob pattern (quoted, e.g. `'sprites/*.png'`) or directory of input files.
+    #[arg(value_name = "input")]
+    input: String,
+
+    /// Output directory template, where `{dir}` is the input's directory and `{stem}` its file
+    /// name without the extension. Each command's output base becomes `{out-dir}/{stem}`.
+    /// Only for commands with an output base argument. Defaults to the command's usual output
+    /// beside each input.
+    #[arg(value_name = "out-dir", long)]
+    out_dir: Option<String>,
+
+    /// Number of files to process at once.
+    #[arg(value_name = "jobs", short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// The command to run and its options, e.g. `pixelate --size 64`.
+    #[arg(
+        value_name = "command",
+        required = true,
+        trailing_var_arg = true,
+        allow_hyphen_values = true
+    )]
+    command: Vec<String>,
+}
+
+/// Parses an image command from arguments without a binary name.
+#[derive(Parser)]
+#[command(name = "batch", no_binary_name = true)]
+struct BatchCommand {
+    #[command(subcommand)]
+    command: TytImage,
+}
+
+impl Batch {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let mut inputs = deps.glob_files(&self.input)?;
+        inputs.retain(|path| ImageFormat::from_path(path).is_some());
+        if inputs.is_empty() {
+            return Err(invalid_data(format!("no files match `{}`", self.input)));
+        }
+
+        // Parse every command up front so usage errors stop the batch before anything runs.
+        let mut commands = Vec::with_capacity(inputs.len());
+        for input in &inputs {
+            let command = BatchCommand::try_parse_from(self.command_args(input)?)
+                .map_err(|e| invalid_data::<Error>(tyt_common::usage_error(&e)))?
+                .command;
+            if matches!(command, TytImage::Batch(_)) {
+                return Err(invalid_data("batch commands cannot be nested"));
+            }
+            commands.push((input, command));
+        }
+
+        let results = tyt_common::run_parallel(&commands, self.jobs, |(input, command)| {
+            if let Some(out_dir) = self.out_dir(input) {
+                deps.create_dir_all(out_dir)?;
+            }
+            command.clone().execute(deps.clone())
+        });
+
+        let labels: Vec<_> = inputs.iter().map(|input| input.display()).collect();
+        deps.write_stdout(tyt_common::batch_summary(&labels, &results).as_bytes())?;
+        let failed = results.iter().filter(|result| result.is_err()).count();
+        if failed > 0 {
+            return Err(
+                IOError::other(format!("{failed} of {} files failed", inputs.len())).into(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the expanded output directory for `input`.
+    fn out_dir(&self, input: &Path) -> Option<String> {
+        let template = self.out_dir.as_ref()?;
+        Some(tyt_common::batch_out_dir(
+            template,
+            input,
+            &file_stem(input),
+        ))
+    }
+
+    /// Returns the arguments for running the command on `input`, with the output base under
+    /// `--out-dir` when given.
+    fn command_args(&self, input: &Path) -> Result<Vec<String>> {
+        let out_base = self
+            .out_dir(input)
+            .map(|out_dir| format!("{out_dir}/{}", file_stem(input)));
+        tyt_common::batch_command_args(&BatchCommand::command(), &self.command, input, out_base)
+            .map_err(invalid_data)
+    }
+}
+
+/// Returns `input`'s file name without its extension.
+fn file_stem(input: &Path) -> String {
+    input
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,116 @@
+use crate::{
+    Dependencies, Error, Result,
+    utilities::{self, DiffMetric},
+};
+use clap::Parser;
+use tyt_common::{ImageFormat, ImageInput, ImageOutput, invalid_data};
+
+/// Compares two images of the same size, printing their MSE, PSNR, SSIM, and largest error per
+/// channel, and writes a heatmap of where they differ.
+///
+/// Both images are compared as floats, so HDR values past 1 count. With `--threshold`, the
+/// command fails when the `--metric` is worse than the threshold, so scripts can gate on
+/// regressions. The threshold is checked before the heatmap is written, so the exit status
+/// reflects it even when writing the heatmap fails.
+#[derive(Clone, Debug, Parser)]
+pub struct Diff {
+    /// Reference image path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.jpg`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Image to compare against the reference, as a path or base name.
+    #[arg(value_name = "other")]
+    other: String,
+
+    /// Heatmap output base name, written as PNG. Defaults to `{base}-diff`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Heatmap output path, with the format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
+    /// Channel difference shown as white in the heatmap, where 1 is the full range of an 8-bit
+    /// image. Defaults to the largest difference found, so any difference shows.
+    #[arg(value_name = "scale", long, value_parser = parse_scale)]
+    scale: Option<f32>,
+
+    /// Fail when the metric is worse than this.
+    #[arg(value_name = "threshold", long)]
+    threshold: Option<f64>,
+
+    /// Metric checked against `--threshold`.
+    #[arg(
+        value_name = "metric",
+        long,
+        value_enum,
+        default_value_t,
+        requires = "threshold"
+    )]
+    metric: DiffMetric,
+}
+
+impl Diff {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let ImageInput {
+            path: in_path,
+            base,
+            ..
+        } = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let other_path = ImageInput::locate(&self.other, |path| deps.file_exists(path))?.path;
+        let out_base = self.out_base.unwrap_or_else(|| format!("{base}-diff"));
+        let out_path =
+            ImageOutput::resolve(self.output.as_deref(), out_base, ImageFormat::Png)?.path();
+        let (image, width, height) = deps.load_image_rgba32f(&in_path)?;
+        let (other, other_width, other_height) = deps.load_image_rgba32f(&other_path)?;
+        if (other_width, other_height) != (width, height) {
+            return Err(invalid_data(format!(
+                "`{other_path}` is {other_width}x{other_height}, but `{in_path}` is {width}x{height}"
+            )));
+        }
+
+        let diff = utilities::diff_images(&image, &other, width, height);
+        let [r, g, b, a] = diff.max_error;
+        deps.write_stdout(
+            format!(
+                "MSE: {:.6}\nPSNR: {:.2} dB\nSSIM: {:.5}\nMax error: R {r:.4}, G {g:.4}, B {b:.4}, A {a:.4}\n",
+                diff.mse, diff.psnr, diff.ssim
+            )
+            .as_bytes(),
+        )?;
+        let exceeded = self.threshold.and_then(|threshold| {
+            let value = self.metric.value(&diff);
+            let failed = if self.metric.higher_is_better() {
+                value < threshold
+            } else {
+                value > threshold
+            };
+            failed.then(|| {
+                Error::ThresholdExceeded(format!(
+                    "{} is {value}, past the threshold of {threshold}",
+                    self.metric.name()
+                ))
+            })
+        });
+
+        let scale = self
+            .scale
+            .unwrap_or_else(|| DiffMetric::MaxError.value(&diff) as f32);
+        let heatmap = utilities::diff_heatmap(&image, &other, width, height, scale);
+        let written = utilities::write_image(&deps, &out_path, &heatmap)
+            .and_then(|()| deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes()));
+        match exceeded {
+            Some(error) => Err(error),
+            None => written,
+        }
+    }
+}
+
+fn parse_scale(value: &str) -> std::result::Result<f32, String> {
+    let scale: f32 = value.parse().map_err(|e| format!("{e}"))?;
+    if !(scale > 0.0 && scale.is_finite()) {
+        return Err(format!("{scale} is not a positive number"));
+    }
+    Ok(scale)
+}
@@ -0,0 +1,90 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, DilationMap, Image},
+};
+use clap::Parser;
+use tyt_common::{ImageInput, ImageOutput, invalid_data};
+
+/// Bleeds the colors at the edges of UV islands outward into the empty texels around them, so
+/// mipmapping and filtering don't pull in black seams.
+///
+/// Islands are the texels with nonzero alpha, or the white texels of `--mask`. Every map of a
+/// texture set given with `--with` is filled from the same island texels as the input.
+#[derive(Clone, Debug, Parser)]
+pub struct Dilate {
+    /// Input image path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.jpg`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name, written in the input's format. Defaults to `{base}-dilated`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
+    /// How many pixels to grow the islands by. Fills every empty texel when omitted.
+    #[arg(value_name = "radius", short, long)]
+    radius: Option<u32>,
+
+    /// Mask image whose white texels mark the islands, instead of the input's alpha. All four
+    /// channels are then filled; with alpha islands, alpha is left as is.
+    #[arg(value_name = "mask", long)]
+    mask: Option<String>,
+
+    /// Other maps of the same texture set, each written as `{base}-dilated` beside itself.
+    #[arg(value_name = "with", long, num_args = 1..)]
+    with: Vec<String>,
+}
+
+impl Dilate {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let ImageInput {
+            path: in_path,
+            base,
+            format,
+        } = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_base = self.out_base.unwrap_or_else(|| format!("{base}-dilated"));
+        let out_path = ImageOutput::resolve(self.output.as_deref(), out_base, format)?.path();
+        let image = utilities::load_image(&deps, &in_path)?;
+
+        let islands = match &self.mask {
+            Some(mask_path) => {
+                let mask = utilities::load_image(&deps, mask_path)?;
+                check_size(&image, &mask, mask_path)?;
+                utilities::islands_from_mask(&mask)
+            }
+            None => utilities::islands_from_alpha(&image),
+        };
+        if !islands.contains(&true) {
+            return Err(invalid_data(format!("`{in_path}` has no island texels")));
+        }
+        let map = DilationMap::new(&islands, image.width, image.height, self.radius);
+        let include_alpha = self.mask.is_some();
+
+        utilities::write_image(&deps, &out_path, &map.apply(&image, include_alpha))?;
+        deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        for other in &self.with {
+            let other = ImageInput::locate(other, |path| deps.file_exists(path))?;
+            let other_image = utilities::load_image(&deps, &other.path)?;
+            check_size(&image, &other_image, &other.path)?;
+            let other_out =
+                ImageOutput::new(format!("{}-dilated", other.base), other.format).path();
+            utilities::write_image(&deps, &other_out, &map.apply(&other_image, include_alpha))?;
+            deps.write_stdout(format!("Wrote: {other_out}\n").as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+fn check_size(image: &Image, other: &Image, other_path: &str) -> Result<()> {
+    if (other.width, other.height) != (image.width, image.height) {
+        return Err(invalid_data(format!(
+            "`{other_path}` is {}x{}, but the input is {}x{}",
+            other.width, other.height, image.width, image.height
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, NormalConvention},
+};
+use clap::Parser;
+use tyt_common::{ImageInput, ImageOutput};
+
+/// Reconstructs a grayscale heightmap from a tangent-space normal map.
+///
+/// Heights are found by integrating the normals' slopes, then stretched so the lowest point is
+/// black and the highest white. Normal maps that aren't from a real surface, such as painted
+/// details, give the closest consistent surface.
+#[derive(Clone, Debug, Parser)]
+pub struct HeightFromNormal {
+    /// Input normal map path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.jpg`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name, written in the input's format. Defaults to `{base}-height`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
+    /// Green channel convention of the input.
+    #[arg(value_name = "convention", long, value_enum, default_value_t)]
+    convention: NormalConvention,
+
+    /// Wrap around the edges, for tiling textures.
+    #[arg(value_name = "tile", long)]
+    tile: bool,
+}
+
+impl HeightFromNormal {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let ImageInput {
+            path: in_path,
+            base,
+            format,
+        } = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_base = self.out_base.unwrap_or_else(|| format!("{base}-height"));
+        let out_path = ImageOutput::resolve(self.output.as_deref(), out_base, format)?.path();
+        let image = utilities::load_image(&deps, &in_path)?;
+
+        let heights = utilities::height_from_normals(&image, self.convention, self.tile);
+        utilities::write_image(&deps, &out_path, &heights.to_image())?;
+        deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, MipContainer, MipFilter, MipOptions, PowerOfTwo, TextureKind},
+};
+use clap::Parser;
+use tyt_common::{ImageInput, ImageOutput};
+
+/// Generates the full mip chain of a texture, down to 1x1.
+///
+/// Writes each level as `{out_base}-{i}`, with level 0 at full resolution, or the whole chain
+/// to `{out_base}.{ktx2,dds}` with `--container`.
+#[derive(Clone, Debug, Parser)]
+pub struct Mipmaps {
+    /// Input image path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.jpg`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name. Defaults to `{base}-mip`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Filter used to shrink each level.
+    #[arg(value_name = "filter", long, value_enum, default_value_t)]
+    filter: MipFilter,
+
+    /// What the texture holds, which decides how it's filtered.
+    #[arg(value_name = "kind", long, value_enum, default_value_t)]
+    kind: TextureKind,
+
+    /// Alpha test cutoff from 0 to 1 for cutout textures such as foliage. Each level's alpha is
+    /// scaled so as many texels pass the test as at full resolution.
+    #[arg(value_name = "alpha-cutoff", long, value_parser = parse_unit)]
+    alpha_cutoff: Option<f32>,
+
+    /// Wrap around the edges when filtering, for tiling textures.
+    #[arg(value_name = "tile", long)]
+    tile: bool,
+
+    /// Bring the image to power-of-two dimensions first, as older targets require.
+    #[arg(value_name = "power-of-two", long, value_enum)]
+    power_of_two: Option<PowerOfTwo>,
+
+    /// Write the chain to one container file instead of an image per level.
+    #[arg(value_name = "container", long, value_enum)]
+    container: Option<MipContainer>,
+}
+
+impl Mipmaps {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let ImageInput {
+            path: in_path,
+            base,
+            format,
+        } = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_base = self.out_base.unwrap_or_else(|| format!("{base}-mip"));
+        let options = MipOptions {
+            filter: self.filter,
+            kind: self.kind,
+            alpha_cutoff: self.alpha_cutoff,
+            wrap: self.tile,
+        };
+        let mut image = utilities::load_image(&deps, &in_path)?;
+        image = match self.power_of_two {
+            Some(PowerOfTwo::Pad) => utilities::pad_to_power_of_two(&image),
+            Some(PowerOfTwo::Resize) => utilities::resize_to_power_of_two(&image, options),
+            None => image,
+        };
+
+        let levels = utilities::generate_mipmaps(&image, options);
+        match self.container {
+            Some(container) => {
+                let out_path = format!("{out_base}.{}", container.extension());
+                let srgb = self.kind == TextureKind::Color;
+                deps.write_file(&out_path, &container.encode(&levels, srgb))?;
+                deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+            }
+            None => {
+                let digits = (levels.len() - 1).to_string().len();
+                for (i, level) in levels.iter().enumerate() {
+                    let out_path =
+                        ImageOutput::new(format!("{out_base}-{i:0digits$}"), format).path();
+                    utilities::write_image(&deps, &out_path, level)?;
+                    deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_unit(value: &str) -> std::result::Result<f32, String> {
+    let unit: f32 = value.parse().map_err(|e| format!("{e}"))?;
+    if !(0.0..=1.0).contains(&unit) {
+        return Err(format!("{unit} is not between 0 and 1"));
+    }
+    Ok(unit)
+}
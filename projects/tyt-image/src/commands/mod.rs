@@ -1,5 +1,27 @@
+mod batch;
+mod diff;
+mod dilate;
+mod height_from_normal;
+mod mipmaps;
+mod normal_convert;
+mod normal_from_height;
+mod normal_renormalize;
+mod pack_atlas;
 mod pixelate;
+mod quantize;
+mod slice;
 mod square_image;
 
+pub use batch::*;
+pub use diff::*;
+pub use dilate::*;
+pub use height_from_normal::*;
+pub use mipmaps::*;
+pub use normal_convert::*;
+pub use normal_from_height::*;
+pub use normal_renormalize::*;
+pub use pack_atlas::*;
 pub use pixelate::*;
+pub use quantize::*;
+pub use slice::*;
 pub use square_image::*;
@@ -0,0 +1,38 @@
+use crate::{Dependencies, Result, utilities};
+use clap::Parser;
+use tyt_common::{ImageInput, ImageOutput};
+
+/// Converts a normal map between the OpenGL (green up) and DirectX (green down) conventions by
+/// inverting its green channel.
+#[derive(Clone, Debug, Parser)]
+pub struct NormalConvert {
+    /// Input normal map path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.jpg`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name, written in the input's format. Defaults to `{base}-flipped`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+}
+
+impl NormalConvert {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let ImageInput {
+            path: in_path,
+            base,
+            format,
+        } = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_base = self.out_base.unwrap_or_else(|| format!("{base}-flipped"));
+        let out_path = ImageOutput::resolve(self.output.as_deref(), out_base, format)?.path();
+        let image = utilities::load_image(&deps, &in_path)?;
+
+        utilities::write_image(&deps, &out_path, &utilities::flip_green(&image))?;
+        deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, GradientKernel, NormalConvention},
+};
+use clap::Parser;
+use tyt_common::{ImageInput, ImageOutput};
+
+/// Generates a tangent-space normal map from a grayscale heightmap, where brighter is higher.
+///
+/// 16-bit heightmaps are read at full precision, so gentle slopes don't step.
+#[derive(Clone, Debug, Parser)]
+pub struct NormalFromHeight {
+    /// Input heightmap path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.jpg`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name, written in the input's format. Defaults to `{base}-normal`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
+    /// How steep the surface is, as the height in pixels of white above black.
+    #[arg(value_name = "strength", short, long, default_value_t = 8.0)]
+    strength: f32,
+
+    /// Derivative filter used to measure slopes.
+    #[arg(value_name = "kernel", long, value_enum, default_value_t)]
+    kernel: GradientKernel,
+
+    /// Green channel convention of the output.
+    #[arg(value_name = "convention", long, value_enum, default_value_t)]
+    convention: NormalConvention,
+
+    /// Wrap around the edges, for tiling textures.
+    #[arg(value_name = "tile", long)]
+    tile: bool,
+}
+
+impl NormalFromHeight {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let ImageInput {
+            path: in_path,
+            base,
+            format,
+        } = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_base = self.out_base.unwrap_or_else(|| format!("{base}-normal"));
+        let out_path = ImageOutput::resolve(self.output.as_deref(), out_base, format)?.path();
+        let heights = utilities::load_height_field(&deps, &in_path)?;
+
+        let normals = utilities::normals_from_height(
+            &heights,
+            self.strength,
+            self.kernel,
+            self.tile,
+            self.convention,
+        );
+        utilities::write_image(&deps, &out_path, &normals)?;
+        deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, MipFilter, MipOptions, TextureKind},
+};
+use clap::Parser;
+use tyt_common::{ImageInput, ImageOutput};
+
+/// Rescales every vector of a normal map to unit length, fixing the shortened normals left by
+/// resizing, blending, or compression.
+///
+/// With `--height`, the map is first resized by filtering its vectors rather than its colors.
+#[derive(Clone, Debug, Parser)]
+pub struct NormalRenormalize {
+    /// Input normal map path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.jpg`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name, written in the input's format. Defaults to `{base}-renormalized`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
+    /// Resize to this height in pixels first, keeping the aspect ratio.
+    #[arg(value_name = "height", long)]
+    height: Option<u32>,
+}
+
+impl NormalRenormalize {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let ImageInput {
+            path: in_path,
+            base,
+            format,
+        } = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_base = self
+            .out_base
+            .unwrap_or_else(|| format!("{base}-renormalized"));
+        let out_path = ImageOutput::resolve(self.output.as_deref(), out_base, format)?.path();
+        let image = utilities::load_image(&deps, &in_path)?;
+
+        let normals = match self.height {
+            Some(height) => {
+                let height = height.max(1);
+                let width = ((image.width as u64 * height as u64) as f64 / image.height as f64)
+                    .round()
+                    .max(1.0) as u32;
+                let options = MipOptions {
+                    filter: MipFilter::default(),
+                    kind: TextureKind::Normal,
+                    alpha_cutoff: None,
+                    wrap: false,
+                };
+                utilities::resize_texture(&image, width, height, options)
+            }
+            None => utilities::renormalize(&image),
+        };
+        utilities::write_image(&deps, &out_path, &normals)?;
+        deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        Ok(())
+    }
+}
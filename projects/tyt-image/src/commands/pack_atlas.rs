@@ -0,0 +1,137 @@
+use crate::{
+    AtlasMetadata, AtlasPage, Dependencies, Result,
+    utilities::{self, AtlasOptions, SpriteImage},
+};
+use clap::Parser;
+use std::path::Path;
+use tyt_common::{ImageFormat, invalid_data};
+
+/// Packs a directory or glob of images into power-of-two atlas pages with a JSON sidecar.
+///
+/// Writes `{out_base}.png`, or `{out_base}-{i}.png` when the sprites need several pages, and
+/// `{out_base}.json` with each sprite's page, rect, rotation, trimmed offset, and original
+/// size. Sprites are named by file stem.
+#[derive(Clone, Debug, Parser)]
+pub struct PackAtlas {
+    /// Glob pattern (quoted, e.g. `'sprites/*.png'`) or directory of sprite images.
+    #[arg(value_name = "input")]
+    input: String,
+
+    /// Output base name for the pages and sidecar.
+    #[arg(value_name = "out-base", default_value = "atlas")]
+    out_base: String,
+
+    /// Largest page width and height in pixels, a power of two.
+    #[arg(value_name = "max-size", long, default_value_t = 2048, value_parser = parse_power_of_two)]
+    max_size: u32,
+
+    /// Empty pixels between neighboring sprites.
+    #[arg(value_name = "padding", long, default_value_t = 2)]
+    padding: u32,
+
+    /// Pixels of each sprite's edge to repeat outward, so texture filtering never picks up a
+    /// neighbor.
+    #[arg(value_name = "extrude", long, default_value_t = 0)]
+    extrude: u32,
+
+    /// Cut away transparent borders before packing.
+    #[arg(value_name = "trim", long, conflicts_with = "grid")]
+    trim: bool,
+
+    /// Allow sprites to be stored turned a quarter turn clockwise when that packs tighter.
+    #[arg(value_name = "rotate", long, conflicts_with = "grid")]
+    rotate: bool,
+
+    /// Lay sprites out as a uniform grid flipbook in file order instead of bin-packing them,
+    /// with every cell the size of the largest sprite.
+    #[arg(value_name = "grid", long)]
+    grid: bool,
+
+    /// Cells per row for `--grid`. Defaults to as close to a square as fits.
+    #[arg(value_name = "columns", long, requires = "grid")]
+    columns: Option<u32>,
+}
+
+impl PackAtlas {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let paths: Vec<_> = deps
+            .glob_files(&self.input)?
+            .into_iter()
+            .filter(|path| ImageFormat::from_path(path).is_some())
+            .collect();
+        if paths.is_empty() {
+            return Err(invalid_data(format!("no images match `{}`", self.input)));
+        }
+        let mut sprites: Vec<SpriteImage> = Vec::with_capacity(paths.len());
+        for path in &paths {
+            let name = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            if sprites.iter().any(|sprite| sprite.name == name) {
+                return Err(invalid_data(format!(
+                    "more than one sprite is named `{name}`"
+                )));
+            }
+            sprites.push(SpriteImage {
+                name,
+                image: utilities::load_image(&deps, path)?,
+            });
+        }
+
+        let options = AtlasOptions {
+            max_size: self.max_size,
+            padding: self.padding,
+            extrude: self.extrude,
+        };
+        let (packed, grid) = if self.grid {
+            let (packed, grid) = utilities::pack_grid(&sprites, options, self.columns)?;
+            (packed, Some(grid))
+        } else {
+            let packed = utilities::pack_sprites(&sprites, options, self.trim, self.rotate)?;
+            (packed, None)
+        };
+
+        let mut pages = Vec::with_capacity(packed.pages.len());
+        for (i, page) in packed.pages.iter().enumerate() {
+            let path = if packed.pages.len() == 1 {
+                format!("{}.png", self.out_base)
+            } else {
+                format!("{}-{i}.png", self.out_base)
+            };
+            utilities::write_image(&deps, &path, page)?;
+            deps.write_stdout(format!("Wrote: {path}\n").as_bytes())?;
+            pages.push(AtlasPage {
+                file: file_name(&path),
+                width: page.width,
+                height: page.height,
+            });
+        }
+        let metadata = AtlasMetadata {
+            pages,
+            sprites: packed.sprites,
+            grid,
+        };
+        let json_path = format!("{}.json", self.out_base);
+        deps.write_file(&json_path, &deps.serialize_atlas_json(&metadata)?)?;
+        deps.write_stdout(format!("Wrote: {json_path}\n").as_bytes())?;
+        Ok(())
+    }
+}
+
+fn parse_power_of_two(value: &str) -> std::result::Result<u32, String> {
+    let size: u32 = value.parse().map_err(|e| format!("{e}"))?;
+    if !size.is_power_of_two() {
+        return Err(format!("{size} is not a power of two"));
+    }
+    Ok(size)
+}
+
+/// Returns the final component of `path`, for naming pages relative to the sidecar.
+fn file_name(path: &str) -> String {
+    Path::new(path).file_name().map_or_else(
+        || path.to_owned(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
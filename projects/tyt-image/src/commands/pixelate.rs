@@ -1,35 +1,60 @@
-use crate::{Dependencies, Result};
+use crate::{
+    Dependencies, Result,
+    utilities::{self, Backend},
+};
 use clap::Parser;
+use tyt_common::{ImageInput, ImageOutput};
 
 /// Pixelates (point-resizes) an image.
 #[derive(Clone, Debug, Parser)]
 pub struct Pixelate {
-    /// Base name for the input image (`{base}.png`).
+    /// Input image path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.jpg`, etc.).
     #[arg(value_name = "base")]
     base: String,
 
-    /// Output base name. Defaults to `{base}-px`.
+    /// Output base name, written in the input's format. Defaults to `{base}-px`.
     #[arg(value_name = "out-base")]
     out_base: Option<String>,
 
+    /// Output path, with the format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
     /// Target height in pixels.
     #[arg(value_name = "size", short, long, default_value_t = 256)]
     size: u32,
+
+    /// Implementation to run. `magick` requires ImageMagick.
+    #[arg(value_name = "backend", long, value_enum, default_value_t)]
+    backend: Backend,
 }
 
 impl Pixelate {
     pub fn execute(self, deps: impl Dependencies) -> Result<()> {
-        let out_base = self.out_base.unwrap_or_else(|| format!("{}-px", self.base));
-        let in_path = format!("{}.png", self.base);
-        let out_path = format!("{out_base}.png");
-        deps.exec_magick([
-            in_path.as_str(),
-            "-filter",
-            "point",
-            "-resize",
-            &format!("x{}", self.size),
-            &out_path,
-        ])?;
+        let ImageInput {
+            path: in_path,
+            base,
+            format,
+        } = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_base = self.out_base.unwrap_or_else(|| format!("{base}-px"));
+        let out_path = ImageOutput::resolve(self.output.as_deref(), out_base, format)?.path();
+        match self.backend {
+            Backend::Native => {
+                let image = utilities::load_image(&deps, &in_path)?;
+                utilities::write_image(&deps, &out_path, &utilities::pixelate(&image, self.size))?;
+            }
+            Backend::Magick => {
+                deps.exec_magick([
+                    in_path.as_str(),
+                    "-filter",
+                    "point",
+                    "-resize",
+                    &format!("x{}", self.size),
+                    &out_path,
+                ])?;
+            }
+        }
         deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
         Ok(())
     }
@@ -0,0 +1,94 @@
+use crate::{
+    Dependencies, Result,
+    utilities::{self, PaletteMethod},
+};
+use clap::Parser;
+use tyt_common::{Dither, ImageInput, ImageOutput, Palette};
+
+/// Reduces an image to a fixed palette, optionally dithering.
+///
+/// The palette is loaded with `--palette` or chosen from the image's own colors with `--colors`.
+/// Colors are matched in the perceptual Oklab space, and alpha is kept as is.
+#[derive(Clone, Debug, Parser)]
+pub struct Quantize {
+    /// Input image path, or a base name tried with each supported extension (`{base}.png`,
+    /// `{base}.jpg`, etc.).
+    #[arg(value_name = "base")]
+    base: String,
+
+    /// Output base name, written in the input's format. Defaults to `{base}-quantized`.
+    #[arg(value_name = "out-base")]
+    out_base: Option<String>,
+
+    /// Output path, with the format taken from its extension.
+    #[arg(value_name = "output", short, long, conflicts_with = "out_base")]
+    output: Option<String>,
+
+    /// Palette file: `.hex`, Paint.NET `.txt`, GIMP `.gpl`, JASC `.pal`, or a swatch image whose
+    /// distinct colors form the palette. Lospec offers palettes in all of these.
+    #[arg(
+        value_name = "palette",
+        long,
+        required_unless_present = "colors",
+        conflicts_with = "colors"
+    )]
+    palette: Option<String>,
+
+    /// Choose this many colors from the image instead of loading a palette.
+    #[arg(value_name = "colors", long, value_parser = clap::value_parser!(u16).range(1..=256))]
+    colors: Option<u16>,
+
+    /// How `--colors` picks its palette.
+    #[arg(
+        value_name = "method",
+        long,
+        value_enum,
+        default_value_t,
+        requires = "colors"
+    )]
+    method: PaletteMethod,
+
+    /// Dithering applied while mapping to the palette.
+    #[arg(value_name = "dither", long, value_enum, default_value_t)]
+    dither: Dither,
+
+    /// Also write the palette used to this `.hex` file, to reuse a chosen palette for other
+    /// images.
+    #[arg(value_name = "save-palette", long)]
+    save_palette: Option<String>,
+}
+
+impl Quantize {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let ImageInput {
+            path: in_path,
+            base,
+            format,
+        } = ImageInput::locate(&self.base, |path| deps.file_exists(path))?;
+        let out_base = self.out_base.unwrap_or_else(|| format!("{base}-quantized"));
+        let out_path = ImageOutput::resolve(self.output.as_deref(), out_base, format)?.path();
+
+        let image = utilities::load_image(&deps, &in_path)?;
+        let palette = match (&self.palette, self.colors) {
+            (Some(path), _) => Palette::load(
+                path,
+                |path| deps.read_file(path),
+                |path| Ok(utilities::load_image(&deps, path)?.to_rgba8()),
+            )?,
+            (None, colors) => {
+                utilities::generate_palette(&image, colors.unwrap_or(16) as usize, self.method)?
+            }
+        };
+        utilities::write_image(
+            &deps,
+            &out_path,
+            &utilities::quantize(&image, &palette, self.dither),
+        )?;
+        deps.write_stdout(format!("Wrote: {out_path}\n").as_bytes())?;
+        if let Some(palette_path) = &self.save_palette {
+            deps.write_file(palette_path, palette.to_hex().as_bytes())?;
+            deps.write_stdout(format!("Wrote: {palette_path}\n").as_bytes())?;
+        }
+        Ok(())
+    }
+}
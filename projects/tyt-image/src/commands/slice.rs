@@ -0,0 +1,193 @@
+use crate::{
+    AtlasRect, Dependencies, Error, Result,
+    utilities::{self, Image},
+};
+use clap::Parser;
+use std::path::Path;
+use tyt_common::{ImageInput, invalid_data};
+
+/// Cuts a sprite sheet into separate frame images.
+///
+/// Frames are found by a grid (`--cell`), by the connected non-transparent regions of the sheet
+/// (`--auto`), or, when the input is a `pack-atlas` JSON sidecar, from the sidecar's rects.
+/// Sidecar sprites are written under their names, turned upright, with trimmed borders
+/// restored. Other frames are written as `{stem}-{i}.png` in reading order.
+#[derive(Clone, Debug, Parser)]
+pub struct Slice {
+    /// Sheet image path, or an atlas JSON sidecar.
+    #[arg(value_name = "input")]
+    input: String,
+
+    /// Directory to write frames to. Defaults to `{base}-frames` beside the input.
+    #[arg(value_name = "out-dir")]
+    out_dir: Option<String>,
+
+    /// Grid cell size in pixels, as `{width}x{height}` or a single size for square cells.
+    #[arg(value_name = "cell", long, value_parser = parse_cell, conflicts_with = "auto")]
+    cell: Option<(u32, u32)>,
+
+    /// Pixels between the sheet's top-left corner and the first cell.
+    #[arg(value_name = "margin", long, default_value_t = 0, requires = "cell")]
+    margin: u32,
+
+    /// Pixels between neighboring cells.
+    #[arg(value_name = "spacing", long, default_value_t = 0, requires = "cell")]
+    spacing: u32,
+
+    /// Also write grid cells that are fully transparent.
+    #[arg(value_name = "keep-empty", long, requires = "cell")]
+    keep_empty: bool,
+
+    /// Find sprites as connected regions of non-transparent pixels.
+    #[arg(value_name = "auto", long)]
+    auto: bool,
+
+    /// Ignore detected regions with fewer pixels than this, such as stray specks.
+    #[arg(
+        value_name = "min-pixels",
+        long,
+        default_value_t = 4,
+        requires = "auto"
+    )]
+    min_pixels: usize,
+
+    /// Write sidecar sprites as stored, without restoring trimmed borders.
+    #[arg(value_name = "keep-trim", long)]
+    keep_trim: bool,
+}
+
+impl Slice {
+    pub fn execute(self, deps: impl Dependencies) -> Result<()> {
+        let is_json = Path::new(&self.input)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let (frames, base) = if is_json {
+            let base = self.input[..self.input.len() - ".json".len()].to_owned();
+            (self.atlas_frames(&deps)?, base)
+        } else {
+            let ImageInput {
+                path: in_path,
+                base,
+                ..
+            } = ImageInput::locate(&self.input, |path| deps.file_exists(path))?;
+            (self.sheet_frames(&deps, &in_path, &base)?, base)
+        };
+        if frames.is_empty() {
+            return Err(invalid_data(format!("no frames found in `{}`", self.input)));
+        }
+
+        let out_dir = self.out_dir.unwrap_or_else(|| format!("{base}-frames"));
+        deps.create_dir_all(&out_dir)?;
+        for (name, frame) in &frames {
+            utilities::write_image(&deps, format!("{out_dir}/{name}.png"), frame)?;
+        }
+        deps.write_stdout(format!("Wrote {} frames to: {out_dir}\n", frames.len()).as_bytes())?;
+        Ok(())
+    }
+
+    /// Cuts every sprite listed in the sidecar out of its page.
+    fn atlas_frames(&self, deps: &impl Dependencies) -> Result<Vec<(String, Image)>> {
+        let metadata = deps.parse_atlas_json(&deps.read_file(&self.input)?)?;
+        let dir = Path::new(&self.input).parent().unwrap_or(Path::new(""));
+        let mut pages = Vec::with_capacity(metadata.pages.len());
+        for page in &metadata.pages {
+            check_file_name("page file", &page.file)?;
+            pages.push(utilities::load_image(deps, dir.join(&page.file))?);
+        }
+        let mut frames = Vec::with_capacity(metadata.sprites.len());
+        for sprite in &metadata.sprites {
+            let page = pages.get(sprite.page).ok_or_else(|| {
+                invalid_data::<Error>(format!(
+                    "sprite `{}` is on page {}, but there are only {} pages",
+                    sprite.name,
+                    sprite.page,
+                    pages.len()
+                ))
+            })?;
+            check_file_name("sprite name", &sprite.name)?;
+            check_bounds(page, sprite.rect, &sprite.name)?;
+            frames.push((
+                sprite.name.clone(),
+                utilities::extract_sprite(page, sprite, self.keep_trim),
+            ));
+        }
+        Ok(frames)
+    }
+
+    /// Cuts frames out of a sheet by grid or by detected regions, naming them by index.
+    fn sheet_frames(
+        &self,
+        deps: &impl Dependencies,
+        in_path: &str,
+        base: &str,
+    ) -> Result<Vec<(String, Image)>> {
+        let sheet = utilities::load_image(deps, in_path)?;
+        let rects = match self.cell {
+            Some((width, height)) => utilities::grid_cells(
+                sheet.width,
+                sheet.height,
+                width,
+                height,
+                self.margin,
+                self.spacing,
+            ),
+            None if self.auto => utilities::detect_sprites(&sheet, self.min_pixels),
+            None => {
+                return Err(invalid_data("slicing an image needs `--cell` or `--auto`"));
+            }
+        };
+        let frames: Vec<Image> = rects
+            .into_iter()
+            .map(|rect| utilities::crop_rect(&sheet, rect))
+            .filter(|frame| self.keep_empty || !frame.is_transparent())
+            .collect();
+        let stem = Path::new(base).file_name().map_or_else(
+            || base.to_owned(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let digits = frames.len().saturating_sub(1).to_string().len();
+        Ok(frames
+            .into_iter()
+            .enumerate()
+            .map(|(i, frame)| (format!("{stem}-{i:0digits$}"), frame))
+            .collect())
+    }
+}
+
+/// Rejects a sidecar `value` that isn't a plain file name, so it can't point outside the
+/// sidecar's or output's directory.
+fn check_file_name(what: &str, value: &str) -> Result<()> {
+    if value.is_empty() || value == "." || value == ".." || value.contains(['/', '\\', ':']) {
+        return Err(invalid_data(format!(
+            "{what} `{value}` in the sidecar is not a plain file name"
+        )));
+    }
+    Ok(())
+}
+
+fn check_bounds(page: &Image, rect: AtlasRect, name: &str) -> Result<()> {
+    let fits = |start: u32, length: u32, limit: u32| {
+        start.checked_add(length).is_some_and(|end| end <= limit)
+    };
+    if !fits(rect.x, rect.width, page.width) || !fits(rect.y, rect.height, page.height) {
+        return Err(invalid_data(format!(
+            "sprite `{name}` lies outside its {}x{} page",
+            page.width, page.height
+        )));
+    }
+    Ok(())
+}
+
+fn parse_cell(value: &str) -> std::result::Result<(u32, u32), String> {
+    let parse = |v: &str| {
+        v.trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|&size| size > 0)
+            .ok_or_else(|| format!("`{value}` is not a cell size like `32` or `32x16`"))
+    };
+    match value.split_once(['x', 'X']) {
+        Some((width, height)) => Ok((parse(width)?, parse(height)?)),
+        None => parse(value).map(|size| (size, size)),
+    }
+}
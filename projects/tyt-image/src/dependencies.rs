@@ -1,12 +1,52 @@
-use crate::Result;
-use std::ffi::OsStr;
+use crate::{AtlasMetadata, Result};
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
 
 /// Dependencies for image operations.
-pub trait Dependencies {
+///
+/// Implementations are shared across threads by the `batch` command.
+pub trait Dependencies: Clone + Sync {
+    fn create_dir_all<P: AsRef<Path>>(&self, path: P) -> Result<()>;
+
     fn exec_magick<I, S>(&self, args: I) -> Result<Vec<u8>>
     where
         I: IntoIterator<Item = S>,
         S: AsRef<OsStr>;
 
+    /// Returns `true` if `path` exists.
+    fn file_exists<P: AsRef<Path>>(&self, path: P) -> Result<bool>;
+
+    /// Returns sorted file paths matching a glob pattern, or the files directly inside `pattern`
+    /// when it names a directory.
+    fn glob_files(&self, pattern: &str) -> Result<Vec<PathBuf>>;
+
+    /// Loads an image and converts it to RGBA8, returning the pixel data, width, and height.
+    fn load_image_rgba<P: AsRef<Path>>(&self, path: P) -> Result<(Vec<u8>, u32, u32)>;
+
+    /// Loads an image and converts it to RGBA32F, returning the pixel data, width, and height.
+    /// Integer formats are mapped to `[0, 1]` without any color space conversion.
+    fn load_image_rgba32f<P: AsRef<Path>>(&self, path: P) -> Result<(Vec<f32>, u32, u32)>;
+
+    /// Parses atlas metadata from JSON bytes.
+    fn parse_atlas_json(&self, json: &[u8]) -> Result<AtlasMetadata>;
+
+    fn read_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>>;
+
+    /// Serializes atlas metadata as pretty-printed JSON.
+    fn serialize_atlas_json(&self, metadata: &AtlasMetadata) -> Result<Vec<u8>>;
+
+    fn write_file<P: AsRef<Path>>(&self, path: P, contents: &[u8]) -> Result<()>;
+
+    /// Writes RGBA8 pixel data, inferring the image format from the path's extension.
+    fn write_image_rgba<P: AsRef<Path>>(
+        &self,
+        path: P,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<()>;
+
     fn write_stdout(&self, contents: &[u8]) -> Result<()>;
 }
@@ -1,10 +1,18 @@
-use crate::{Dependencies, Error, Result};
-use std::ffi::OsStr;
+use crate::{AtlasMetadata, Dependencies, Error, Result};
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct DependenciesImpl;
 
 impl Dependencies for DependenciesImpl {
+    fn create_dir_all<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Ok(fs::create_dir_all(path.as_ref())?)
+    }
+
     fn exec_magick<I, S>(&self, args: I) -> Result<Vec<u8>>
     where
         I: IntoIterator<Item = S>,
@@ -13,6 +21,55 @@ impl Dependencies for DependenciesImpl {
         tyt_injection::exec_map("magick", args, Error::IO, Error::Magick)
     }
 
+    fn file_exists<P: AsRef<Path>>(&self, path: P) -> Result<bool> {
+        Ok(fs::exists(path.as_ref())?)
+    }
+
+    fn glob_files(&self, pattern: &str) -> Result<Vec<PathBuf>> {
+        Ok(tyt_injection::glob_files(pattern)?)
+    }
+
+    fn load_image_rgba<P: AsRef<Path>>(&self, path: P) -> Result<(Vec<u8>, u32, u32)> {
+        Ok(tyt_injection::load_image_rgba(path.as_ref())?)
+    }
+
+    fn load_image_rgba32f<P: AsRef<Path>>(&self, path: P) -> Result<(Vec<f32>, u32, u32)> {
+        Ok(tyt_injection::load_image_rgba32f(path.as_ref())?)
+    }
+
+    fn parse_atlas_json(&self, json: &[u8]) -> Result<AtlasMetadata> {
+        Ok(tyt_injection::parse_json(json)?)
+    }
+
+    fn read_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>> {
+        Ok(tyt_injection::read_file(path.as_ref())?)
+    }
+
+    fn serialize_atlas_json(&self, metadata: &AtlasMetadata) -> Result<Vec<u8>> {
+        let mut bytes = tyt_injection::serialize_json_pretty(metadata)?;
+        bytes.push(b'\n');
+        Ok(bytes)
+    }
+
+    fn write_file<P: AsRef<Path>>(&self, path: P, contents: &[u8]) -> Result<()> {
+        Ok(tyt_injection::write_file(path.as_ref(), contents)?)
+    }
+
+    fn write_image_rgba<P: AsRef<Path>>(
+        &self,
+        path: P,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<()> {
+        Ok(tyt_injection::write_image_rgba(
+            path.as_ref(),
+            pixels,
+            width,
+            height,
+        )?)
+    }
+
     fn write_stdout(&self, contents: &[u8]) -> Result<()> {
         Ok(tyt_injection::write_stdout(contents)?)
     }
@@ -10,6 +10,7 @@ use tyt_common::ExecFailed;
 pub enum Error {
     Magick(ExecFailed),
     IO(IOError),
+    ThresholdExceeded(String),
 }
 
 impl Display for Error {
@@ -33,6 +34,7 @@ impl Display for Error {
                 Ok(())
             }
             Error::IO(e) => e.fmt(f),
+            Error::ThresholdExceeded(message) => write!(f, "images differ: {message}"),
         }
     }
 }
@@ -40,7 +42,7 @@ impl Display for Error {
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self {
-            Error::Magick(_) => None,
+            Error::Magick(_) | Error::ThresholdExceeded(_) => None,
             Error::IO(e) => Some(e),
         }
     }
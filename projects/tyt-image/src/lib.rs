@@ -1,5 +1,8 @@
 pub mod commands;
 
+pub(crate) mod utilities;
+
+mod atlas_metadata;
 mod dependencies;
 #[cfg(feature = "impl")]
 mod dependencies_impl;
@@ -7,6 +10,7 @@ mod error;
 mod result;
 mod tyt_image;
 
+pub use atlas_metadata::*;
 pub use dependencies::*;
 #[cfg(feature = "impl")]
 pub use dependencies_impl::*;
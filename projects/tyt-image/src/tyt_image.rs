@@ -8,15 +8,59 @@ pub enum TytImage {
     #[command(name = "pixelate")]
     Pixelate(commands::Pixelate),
 
+    #[command(name = "diff")]
+    Diff(commands::Diff),
+
+    #[command(name = "dilate")]
+    Dilate(commands::Dilate),
+
+    #[command(name = "height-from-normal")]
+    HeightFromNormal(commands::HeightFromNormal),
+
+    #[command(name = "mipmaps")]
+    Mipmaps(commands::Mipmaps),
+
+    #[command(name = "normal-convert")]
+    NormalConvert(commands::NormalConvert),
+
+    #[command(name = "normal-from-height")]
+    NormalFromHeight(commands::NormalFromHeight),
+
+    #[command(name = "normal-renormalize")]
+    NormalRenormalize(commands::NormalRenormalize),
+
+    #[command(name = "pack-atlas")]
+    PackAtlas(commands::PackAtlas),
+
+    #[command(name = "quantize")]
+    Quantize(commands::Quantize),
+
+    #[command(name = "slice")]
+    Slice(commands::Slice),
+
     #[command(name = "square-image")]
     SquareImage(commands::SquareImage),
+
+    #[command(name = "batch")]
+    Batch(commands::Batch),
 }
 
 impl TytImage {
     pub fn execute(self, dependencies: impl crate::Dependencies) -> crate::Result<()> {
         match self {
             TytImage::Pixelate(cmd) => cmd.execute(dependencies),
+            TytImage::Diff(cmd) => cmd.execute(dependencies),
+            TytImage::Dilate(cmd) => cmd.execute(dependencies),
+            TytImage::HeightFromNormal(cmd) => cmd.execute(dependencies),
+            TytImage::Mipmaps(cmd) => cmd.execute(dependencies),
+            TytImage::NormalConvert(cmd) => cmd.execute(dependencies),
+            TytImage::NormalFromHeight(cmd) => cmd.execute(dependencies),
+            TytImage::NormalRenormalize(cmd) => cmd.execute(dependencies),
+            TytImage::PackAtlas(cmd) => cmd.execute(dependencies),
+            TytImage::Quantize(cmd) => cmd.execute(dependencies),
+            TytImage::Slice(cmd) => cmd.execute(dependencies),
             TytImage::SquareImage(cmd) => cmd.execute(dependencies),
+            TytImage::Batch(cmd) => cmd.execute(dependencies),
         }
     }
 }
@@ -0,0 +1,306 @@
+use crate::{
+    AtlasGrid, AtlasPoint, AtlasRect, AtlasSize, AtlasSprite, Result,
+    utilities::{Image, MaxRects},
+};
+use tyt_common::invalid_data;
+
+/// An image to pack, named for the atlas sidecar.
+#[derive(Clone, Debug)]
+pub struct SpriteImage {
+    pub name: String,
+    pub image: Image,
+}
+
+/// Settings shared by both atlas modes.
+#[derive(Clone, Copy, Debug)]
+pub struct AtlasOptions {
+    /// Largest page side, a power of two.
+    pub max_size: u32,
+
+    /// Empty pixels between neighboring sprites.
+    pub padding: u32,
+
+    /// Pixels of each sprite's edge repeated outward, so filtering never samples a neighbor.
+    pub extrude: u32,
+}
+
+/// Packed pages along with where each sprite went, in input order.
+#[derive(Clone, Debug)]
+pub struct PackedAtlas {
+    pub pages: Vec<Image>,
+    pub sprites: Vec<AtlasSprite>,
+}
+
+/// Returns the part of `image` inside its non-transparent pixels and that part's top-left
+/// corner. A fully transparent image trims to its top-left pixel.
+pub fn trim_transparent(image: &Image) -> (Image, AtlasPoint) {
+    let opaque = |x: u32, y: u32| image.get(x, y)[3] > 0;
+    let rows: Vec<u32> = (0..image.height)
+        .filter(|&y| (0..image.width).any(|x| opaque(x, y)))
+        .collect();
+    let columns: Vec<u32> = (0..image.width)
+        .filter(|&x| (0..image.height).any(|y| opaque(x, y)))
+        .collect();
+    let (Some(&top), Some(&bottom), Some(&left), Some(&right)) =
+        (rows.first(), rows.last(), columns.first(), columns.last())
+    else {
+        return (image.crop(0, 0, 1, 1), AtlasPoint::default());
+    };
+    (
+        image.crop(left, top, right - left + 1, bottom - top + 1),
+        AtlasPoint { x: left, y: top },
+    )
+}
+
+/// Bin-packs sprites into as few power-of-two pages as possible, each as small as it can be.
+///
+/// Sprites may be turned a quarter turn clockwise when `allow_rotation` is set. With `trim`,
+/// transparent borders are cut away first and recorded as each sprite's offset.
+pub fn pack_sprites(
+    sprites: &[SpriteImage],
+    options: AtlasOptions,
+    trim: bool,
+    allow_rotation: bool,
+) -> Result<PackedAtlas> {
+    let mut images = Vec::with_capacity(sprites.len());
+    for sprite in sprites {
+        let (image, offset) = if trim {
+            trim_transparent(&sprite.image)
+        } else {
+            (sprite.image.clone(), AtlasPoint::default())
+        };
+        let (width, height) = footprint(&image, options);
+        let fits = |w: u32, h: u32| {
+            w <= options.max_size + options.padding && h <= options.max_size + options.padding
+        };
+        if !(fits(width, height) || allow_rotation && fits(height, width)) {
+            return Err(too_large(sprite, options.max_size));
+        }
+        images.push((image, offset));
+    }
+
+    // Place large sprites first, which packs tighter.
+    let mut remaining: Vec<usize> = (0..sprites.len()).collect();
+    remaining.sort_by_key(|&i| {
+        let image = &images[i].0;
+        std::cmp::Reverse((image.width.max(image.height), image.width * image.height))
+    });
+
+    let mut pages = Vec::new();
+    let mut placements: Vec<Option<AtlasSprite>> = vec![None; sprites.len()];
+    while !remaining.is_empty() {
+        let footprints: Vec<(u32, u32)> = remaining
+            .iter()
+            .map(|&i| footprint(&images[i].0, options))
+            .collect();
+        // Fill a full-size page first to learn which sprites it takes, then find the smallest
+        // page that holds just those.
+        let full = try_pack(
+            &footprints,
+            options.max_size,
+            options.max_size,
+            options,
+            allow_rotation,
+        );
+        let taken: Vec<usize> = full
+            .iter()
+            .enumerate()
+            .filter_map(|(k, placed)| placed.map(|_| k))
+            .collect();
+        let taken_footprints: Vec<(u32, u32)> = taken.iter().map(|&k| footprints[k]).collect();
+        let (page_width, page_height, placed) = page_sizes(options.max_size)
+            .into_iter()
+            .find_map(|(w, h)| {
+                let placed = try_pack(&taken_footprints, w, h, options, allow_rotation);
+                placed.iter().all(Option::is_some).then_some((w, h, placed))
+            })
+            .unwrap_or((
+                options.max_size,
+                options.max_size,
+                full.into_iter().flatten().map(Some).collect(),
+            ));
+
+        let page_index = pages.len();
+        let mut page = Image::new(page_width, page_height);
+        for (&k, placement) in taken.iter().zip(placed) {
+            let Some((rect, rotated)) = placement else {
+                continue;
+            };
+            let index = remaining[k];
+            let (image, offset) = &images[index];
+            let stored = if rotated {
+                image.rotate_cw()
+            } else {
+                image.clone()
+            };
+            let origin = (rect.x + options.extrude, rect.y + options.extrude);
+            blit_extruded(&mut page, &stored, origin, options.extrude);
+            placements[index] = Some(AtlasSprite {
+                name: sprites[index].name.clone(),
+                page: page_index,
+                rect: AtlasRect {
+                    x: origin.0,
+                    y: origin.1,
+                    width: stored.width,
+                    height: stored.height,
+                },
+                rotated,
+                offset: *offset,
+                source_size: AtlasSize {
+                    width: sprites[index].image.width,
+                    height: sprites[index].image.height,
+                },
+            });
+        }
+        pages.push(page);
+        let taken_indices: Vec<usize> = taken.iter().map(|&k| remaining[k]).collect();
+        remaining.retain(|i| !taken_indices.contains(i));
+    }
+
+    Ok(PackedAtlas {
+        pages,
+        sprites: placements.into_iter().flatten().collect(),
+    })
+}
+
+/// Lays frames out in a uniform grid of cells the size of the largest frame, in reading order,
+/// starting new pages as needed. Uses `columns` cells per row when given, otherwise as close to
+/// a square as fits.
+pub fn pack_grid(
+    sprites: &[SpriteImage],
+    options: AtlasOptions,
+    columns: Option<u32>,
+) -> Result<(PackedAtlas, AtlasGrid)> {
+    let cell_width = sprites.iter().map(|s| s.image.width).max().unwrap_or(1);
+    let cell_height = sprites.iter().map(|s| s.image.height).max().unwrap_or(1);
+    let margin = options.extrude;
+    let spacing = 2 * options.extrude + options.padding;
+    let pitch = (cell_width + spacing, cell_height + spacing);
+    // Cells that fit across and down a full page, counting the trailing spacing as padding.
+    let max_columns = (options.max_size + options.padding) / pitch.0;
+    let max_rows = (options.max_size + options.padding) / pitch.1;
+    if max_columns == 0 || max_rows == 0 {
+        let largest = sprites
+            .iter()
+            .max_by_key(|s| s.image.width.max(s.image.height))
+            .expect("a grid has at least one frame");
+        return Err(too_large(largest, options.max_size));
+    }
+
+    let count = sprites.len() as u32;
+    let columns = columns
+        .unwrap_or_else(|| (count as f64).sqrt().ceil() as u32)
+        .clamp(1, max_columns);
+    let rows = count.div_ceil(columns).clamp(1, max_rows);
+    let page_width = (columns * pitch.0 - options.padding).next_power_of_two();
+    let page_height = (rows * pitch.1 - options.padding).next_power_of_two();
+
+    let per_page = (columns * rows) as usize;
+    let mut pages = Vec::new();
+    let mut placed = Vec::with_capacity(sprites.len());
+    for (i, sprite) in sprites.iter().enumerate() {
+        let (page_index, cell) = (i / per_page, (i % per_page) as u32);
+        if page_index == pages.len() {
+            pages.push(Image::new(page_width, page_height));
+        }
+        let origin = (
+            margin + (cell % columns) * pitch.0,
+            margin + (cell / columns) * pitch.1,
+        );
+        blit_extruded(
+            &mut pages[page_index],
+            &sprite.image,
+            origin,
+            options.extrude,
+        );
+        placed.push(AtlasSprite {
+            name: sprite.name.clone(),
+            page: page_index,
+            rect: AtlasRect {
+                x: origin.0,
+                y: origin.1,
+                width: sprite.image.width,
+                height: sprite.image.height,
+            },
+            rotated: false,
+            offset: AtlasPoint::default(),
+            source_size: AtlasSize {
+                width: sprite.image.width,
+                height: sprite.image.height,
+            },
+        });
+    }
+    let grid = AtlasGrid {
+        columns,
+        rows,
+        cell_width,
+        cell_height,
+        margin,
+        spacing,
+    };
+    Ok((
+        PackedAtlas {
+            pages,
+            sprites: placed,
+        },
+        grid,
+    ))
+}
+
+/// Returns the space a sprite takes in the packer: its pixels, extrusion on every side, and
+/// padding on the right and bottom. Pages get the same padding added, so padding only ends up
+/// between sprites.
+fn footprint(image: &Image, options: AtlasOptions) -> (u32, u32) {
+    let extra = 2 * options.extrude + options.padding;
+    (image.width + extra, image.height + extra)
+}
+
+/// Packs `footprints` in order into a `width` by `height` page, returning where each went.
+fn try_pack(
+    footprints: &[(u32, u32)],
+    width: u32,
+    height: u32,
+    options: AtlasOptions,
+    allow_rotation: bool,
+) -> Vec<Option<(AtlasRect, bool)>> {
+    let mut bin = MaxRects::new(width + options.padding, height + options.padding);
+    footprints
+        .iter()
+        .map(|&(w, h)| bin.insert(w, h, allow_rotation))
+        .collect()
+}
+
+/// Returns every power-of-two page size up to `max_size` on a side, smallest area first and
+/// squarer pages first among equal areas.
+fn page_sizes(max_size: u32) -> Vec<(u32, u32)> {
+    let sides: Vec<u32> = (0..=max_size.ilog2()).map(|bit| 1 << bit).collect();
+    let mut sizes: Vec<(u32, u32)> = sides
+        .iter()
+        .flat_map(|&w| sides.iter().map(move |&h| (w, h)))
+        .collect();
+    sizes.sort_by_key(|&(w, h)| (w as u64 * h as u64, w.max(h), std::cmp::Reverse(w)));
+    sizes
+}
+
+/// Copies `image` into `page` at `origin`, repeating its edge pixels `extrude` pixels outward.
+fn blit_extruded(page: &mut Image, image: &Image, origin: (u32, u32), extrude: u32) {
+    let (x0, y0) = (origin.0 as i64, origin.1 as i64);
+    let e = extrude as i64;
+    for y in y0 - e..y0 + image.height as i64 + e {
+        for x in x0 - e..x0 + image.width as i64 + e {
+            if x < 0 || y < 0 || x >= page.width as i64 || y >= page.height as i64 {
+                continue;
+            }
+            let sx = (x - x0).clamp(0, image.width as i64 - 1) as u32;
+            let sy = (y - y0).clamp(0, image.height as i64 - 1) as u32;
+            page.set(x as u32, y as u32, image.get(sx, sy));
+        }
+    }
+}
+
+fn too_large(sprite: &SpriteImage, max_size: u32) -> crate::Error {
+    invalid_data(format!(
+        "sprite `{}` ({}x{}) does not fit in a {max_size}x{max_size} page",
+        sprite.name, sprite.image.width, sprite.image.height
+    ))
+}
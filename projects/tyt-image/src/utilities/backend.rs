@@ -0,0 +1,12 @@
+use clap::ValueEnum;
+
+/// Which implementation runs an image operation.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Backend {
+    /// Process the image in-process.
+    #[default]
+    Native,
+
+    /// Run ImageMagick's `magick`, which must be installed.
+    Magick,
+}
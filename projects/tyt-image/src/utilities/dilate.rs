@@ -0,0 +1,107 @@
+use crate::utilities::Image;
+
+/// For each texel, the island texel whose color it takes when dilating, found once and applied
+/// to every map of a texture set so they stay consistent.
+#[derive(Clone, Debug)]
+pub struct DilationMap {
+    /// Index of the texel to copy from, or `None` to leave the texel as is. Island texels map to
+    /// themselves.
+    pub sources: Vec<Option<usize>>,
+}
+
+impl DilationMap {
+    /// Finds the nearest island texel for every texel with a jump flood, keeping only those
+    /// within `radius` pixels when given.
+    ///
+    /// Jump flooding propagates the nearest known island texel across halving step sizes, which
+    /// covers any distance in `log2(size)` passes. A final one-pixel pass fixes most of the
+    /// cases where it picks a slightly farther texel.
+    pub fn new(islands: &[bool], width: u32, height: u32, radius: Option<u32>) -> Self {
+        let (w, h) = (width as i64, height as i64);
+        let mut nearest: Vec<Option<usize>> = islands
+            .iter()
+            .enumerate()
+            .map(|(i, &island)| island.then_some(i))
+            .collect();
+        let distance_squared = |from: usize, to: usize| {
+            let (fx, fy) = ((from as i64) % w, (from as i64) / w);
+            let (tx, ty) = ((to as i64) % w, (to as i64) / w);
+            (fx - tx).pow(2) + (fy - ty).pow(2)
+        };
+
+        let mut steps = Vec::new();
+        let mut step = (width.max(height).next_power_of_two() / 2).max(1) as i64;
+        while step >= 1 {
+            steps.push(step);
+            step /= 2;
+        }
+        steps.push(1);
+        for step in steps {
+            let previous = nearest.clone();
+            for y in 0..h {
+                for x in 0..w {
+                    let here = (y * w + x) as usize;
+                    let mut best = previous[here];
+                    for dy in [-step, 0, step] {
+                        for dx in [-step, 0, step] {
+                            let (nx, ny) = (x + dx, y + dy);
+                            if nx < 0 || ny < 0 || nx >= w || ny >= h {
+                                continue;
+                            }
+                            let Some(candidate) = previous[(ny * w + nx) as usize] else {
+                                continue;
+                            };
+                            if best.is_none_or(|b| {
+                                distance_squared(here, candidate) < distance_squared(here, b)
+                            }) {
+                                best = Some(candidate);
+                            }
+                        }
+                    }
+                    nearest[here] = best;
+                }
+            }
+        }
+
+        if let Some(radius) = radius {
+            let limit = radius as i64 * radius as i64;
+            for (here, source) in nearest.iter_mut().enumerate() {
+                if source.is_some_and(|s| distance_squared(here, s) > limit) {
+                    *source = None;
+                }
+            }
+        }
+        Self { sources: nearest }
+    }
+
+    /// Returns `image` with every texel outside the islands recolored from its source texel.
+    /// Alpha is copied too when `include_alpha` is set and kept otherwise.
+    pub fn apply(&self, image: &Image, include_alpha: bool) -> Image {
+        let mut out = image.clone();
+        for (pixel, source) in out.pixels.iter_mut().zip(&self.sources) {
+            if let Some(source) = *source {
+                let color = image.pixels[source];
+                let channels = if include_alpha { 4 } else { 3 };
+                pixel[..channels].copy_from_slice(&color[..channels]);
+            }
+        }
+        out
+    }
+}
+
+/// Marks the texels of `image` with nonzero alpha as island texels.
+pub fn islands_from_alpha(image: &Image) -> Vec<bool> {
+    image.pixels.iter().map(|p| p[3] > 0).collect()
+}
+
+/// Marks the texels of a mask image that are at least half white and half opaque as island
+/// texels.
+pub fn islands_from_mask(mask: &Image) -> Vec<bool> {
+    mask.pixels
+        .iter()
+        .map(|&[r, g, b, a]| {
+            let luma = 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+            luma * a as f32 / 255.0 >= 127.5
+        })
+        .collect()
+}
@@ -0,0 +1,82 @@
+use crate::utilities::Image;
+use tyt_common::{DiffusionWeight, Dither, Palette};
+
+/// Reduces `image` to the colors of `palette`.
+///
+/// Dithering works on sRGB-encoded color and matching happens in Oklab. Alpha is kept as is,
+/// and fully transparent pixels are left untouched and take no diffused error.
+pub fn quantize(image: &Image, palette: &Palette, dither: Dither) -> Image {
+    if let Some(bits) = dither.bayer_bits() {
+        ordered(image, palette, bits)
+    } else if let Some(kernel) = dither.kernel() {
+        diffuse_error(image, palette, kernel)
+    } else {
+        map_pixels(image, palette, |_, _| 0.0)
+    }
+}
+
+/// Maps each opaque pixel to the palette color nearest it after adding `offset(x, y)` to every
+/// channel.
+fn map_pixels(image: &Image, palette: &Palette, offset: impl Fn(u32, u32) -> f32) -> Image {
+    let mut out = image.clone();
+    for y in 0..image.height {
+        for x in 0..image.width {
+            let pixel = image.get(x, y);
+            if pixel[3] == 0 {
+                continue;
+            }
+            let offset = offset(x, y);
+            let color = [0, 1, 2].map(|c| pixel[c] as f32 / 255.0 + offset);
+            let [r, g, b] = palette.colors[palette.nearest(color)];
+            out.set(x, y, [r, g, b, pixel[3]]);
+        }
+    }
+    out
+}
+
+/// Ordered dithering with a Bayer matrix `2^bits` pixels on a side, offsetting colors by up to
+/// half the palette's typical step either way.
+fn ordered(image: &Image, palette: &Palette, bits: u32) -> Image {
+    let spread = palette.mean_spacing();
+    map_pixels(image, palette, |x, y| {
+        (tyt_common::bayer_threshold(x, y, bits) - 0.5) * spread
+    })
+}
+
+/// Error diffusion with `kernel`, scanning rows in alternating directions so error doesn't
+/// drift to one side.
+fn diffuse_error(image: &Image, palette: &Palette, kernel: &[DiffusionWeight]) -> Image {
+    let (width, height) = (image.width as i64, image.height as i64);
+    let mut out = image.clone();
+    let mut error = vec![[0.0f32; 3]; image.pixels.len()];
+    for y in 0..height {
+        let forward = if y % 2 == 0 { 1 } else { -1 };
+        for i in 0..width {
+            let x = if forward > 0 { i } else { width - 1 - i };
+            let here = (y * width + x) as usize;
+            let pixel = image.pixels[here];
+            if pixel[3] == 0 {
+                continue;
+            }
+            let color =
+                [0, 1, 2].map(|c| (pixel[c] as f32 / 255.0 + error[here][c]).clamp(0.0, 1.0));
+            let chosen = palette.colors[palette.nearest(color)];
+            out.pixels[here] = [chosen[0], chosen[1], chosen[2], pixel[3]];
+
+            for &(dx, dy, weight) in kernel {
+                let (tx, ty) = (x + dx * forward, y + dy);
+                if !(0..width).contains(&tx) || ty >= height {
+                    continue;
+                }
+                let target = (ty * width + tx) as usize;
+                if image.pixels[target][3] == 0 {
+                    continue;
+                }
+                for c in 0..3 {
+                    error[target][c] += (color[c] - chosen[c] as f32 / 255.0) * weight;
+                }
+            }
+        }
+    }
+    out
+}
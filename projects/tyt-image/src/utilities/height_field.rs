@@ -0,0 +1,62 @@
+use crate::{Dependencies, Result, utilities::Image};
+use std::path::Path;
+
+/// A grid of heights, with pixels stored row by row from the top left.
+#[derive(Clone, Debug, Default)]
+pub struct HeightField {
+    pub width: u32,
+    pub height: u32,
+    pub values: Vec<f32>,
+}
+
+impl HeightField {
+    /// Returns the height at `(x, y)`, wrapping around the edges when `wrap` is set and clamping
+    /// to them otherwise.
+    pub fn get(&self, x: i64, y: i64, wrap: bool) -> f32 {
+        let (w, h) = (self.width as i64, self.height as i64);
+        let (x, y) = if wrap {
+            (x.rem_euclid(w), y.rem_euclid(h))
+        } else {
+            (x.clamp(0, w - 1), y.clamp(0, h - 1))
+        };
+        self.values[(y * w + x) as usize]
+    }
+
+    /// Returns the heights as a grayscale image, stretched so the lowest is black and the
+    /// highest white.
+    pub fn to_image(&self) -> Image {
+        let (min, max) = self
+            .values
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            });
+        let range = if max > min { max - min } else { 1.0 };
+        Image {
+            width: self.width,
+            height: self.height,
+            pixels: self
+                .values
+                .iter()
+                .map(|&v| {
+                    let level = ((v - min) / range * 255.0).round() as u8;
+                    [level, level, level, 255]
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Loads a heightmap through `deps` at full precision, taking the mean of the color channels
+/// as the height in `[0, 1]`.
+pub fn load_height_field(deps: &impl Dependencies, path: impl AsRef<Path>) -> Result<HeightField> {
+    let (pixels, width, height) = deps.load_image_rgba32f(path)?;
+    Ok(HeightField {
+        width,
+        height,
+        values: pixels
+            .chunks_exact(4)
+            .map(|p| (p[0] + p[1] + p[2]) / 3.0)
+            .collect(),
+    })
+}
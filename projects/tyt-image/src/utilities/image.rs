@@ -0,0 +1,107 @@
+use crate::{Dependencies, Result};
+use std::path::Path;
+
+/// An RGBA8 image held in memory, with pixels stored row by row from the top left.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    /// Creates a fully transparent image.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0; 4]; width as usize * height as usize],
+        }
+    }
+
+    /// Creates an image from RGBA8 pixel data.
+    pub fn from_rgba8(pixels: &[u8], width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: pixels
+                .chunks_exact(4)
+                .map(|p| [p[0], p[1], p[2], p[3]])
+                .collect(),
+        }
+    }
+
+    /// Returns the image as RGBA8 pixel data.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    /// Returns the pixel at `(x, y)`.
+    pub fn get(&self, x: u32, y: u32) -> [u8; 4] {
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    /// Sets the pixel at `(x, y)`.
+    pub fn set(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        self.pixels[(y * self.width + x) as usize] = color;
+    }
+
+    /// Returns the `width` by `height` region with its top-left corner at `(x, y)`.
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Image {
+        let mut out = Image::new(width, height);
+        for dy in 0..height {
+            for dx in 0..width {
+                out.set(dx, dy, self.get(x + dx, y + dy));
+            }
+        }
+        out
+    }
+
+    /// Returns the image turned a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Image {
+        let mut out = Image::new(self.height, self.width);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                out.set(self.height - 1 - y, x, self.get(x, y));
+            }
+        }
+        out
+    }
+
+    /// Returns the image turned a quarter turn counterclockwise.
+    pub fn rotate_ccw(&self) -> Image {
+        let mut out = Image::new(self.height, self.width);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                out.set(y, self.width - 1 - x, self.get(x, y));
+            }
+        }
+        out
+    }
+
+    /// Returns `true` if every pixel is fully transparent.
+    pub fn is_transparent(&self) -> bool {
+        self.pixels.iter().all(|p| p[3] == 0)
+    }
+
+    /// Copies `src` into this image with its top-left corner at `(x, y)`, clipping anything that
+    /// falls outside.
+    pub fn blit(&mut self, src: &Image, x: u32, y: u32) {
+        for sy in 0..src.height.min(self.height.saturating_sub(y)) {
+            for sx in 0..src.width.min(self.width.saturating_sub(x)) {
+                self.set(x + sx, y + sy, src.get(sx, sy));
+            }
+        }
+    }
+}
+
+/// Loads an image through `deps`.
+pub fn load_image(deps: &impl Dependencies, path: impl AsRef<Path>) -> Result<Image> {
+    let (pixels, width, height) = deps.load_image_rgba(path)?;
+    Ok(Image::from_rgba8(&pixels, width, height))
+}
+
+/// Writes an image through `deps`, with the format taken from the path's extension.
+pub fn write_image(deps: &impl Dependencies, path: impl AsRef<Path>, image: &Image) -> Result<()> {
+    deps.write_image_rgba(path, &image.to_rgba8(), image.width, image.height)
+}
@@ -0,0 +1,204 @@
+use crate::utilities::Image;
+use clap::ValueEnum;
+
+/// A measure of how much two images differ.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum DiffMetric {
+    /// Mean squared error over all four channels; fails above the threshold.
+    Mse,
+
+    /// Peak signal-to-noise ratio in decibels; fails below the threshold.
+    Psnr,
+
+    /// Structural similarity of luma, from 1 for identical down; fails below the threshold.
+    #[default]
+    Ssim,
+
+    /// Largest difference in any channel; fails above the threshold.
+    MaxError,
+}
+
+impl DiffMetric {
+    /// Returns this metric's value in `diff`.
+    pub fn value(self, diff: &ImageDiff) -> f64 {
+        match self {
+            DiffMetric::Mse => diff.mse,
+            DiffMetric::Psnr => diff.psnr,
+            DiffMetric::Ssim => diff.ssim,
+            DiffMetric::MaxError => diff.max_error.into_iter().fold(0.0, f64::max),
+        }
+    }
+
+    /// Returns the metric's name as given on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            DiffMetric::Mse => "mse",
+            DiffMetric::Psnr => "psnr",
+            DiffMetric::Ssim => "ssim",
+            DiffMetric::MaxError => "max-error",
+        }
+    }
+
+    /// Returns `true` if larger values mean the images are more alike.
+    pub fn higher_is_better(self) -> bool {
+        matches!(self, DiffMetric::Psnr | DiffMetric::Ssim)
+    }
+}
+
+/// How much two images of the same size differ, with channel values where 1 is the full range
+/// of an 8-bit image and HDR values may go beyond it.
+#[derive(Clone, Copy, Debug)]
+pub struct ImageDiff {
+    /// Mean squared error over all four channels.
+    pub mse: f64,
+
+    /// Peak signal-to-noise ratio in decibels, infinite for identical images. The peak is 1, or
+    /// the reference's brightest channel value when that's higher.
+    pub psnr: f64,
+
+    /// Mean structural similarity of luma over Gaussian windows, 1 for identical images.
+    pub ssim: f64,
+
+    /// Largest difference in each of red, green, blue, and alpha.
+    pub max_error: [f64; 4],
+}
+
+/// Standard deviation in pixels of the SSIM window.
+const SSIM_SIGMA: f64 = 1.5;
+
+/// Radius in pixels of the SSIM window, giving the usual 11x11 window.
+const SSIM_RADIUS: i64 = 5;
+
+/// SSIM stabilizers, as fractions of the peak value.
+const SSIM_K1: f64 = 0.01;
+const SSIM_K2: f64 = 0.03;
+
+/// Compares two RGBA32F images of the same size, given as row-major pixel data.
+pub fn diff_images(a: &[f32], b: &[f32], width: u32, height: u32) -> ImageDiff {
+    let mut squared = 0.0;
+    let mut max_error = [0.0f64; 4];
+    for (pa, pb) in a.chunks_exact(4).zip(b.chunks_exact(4)) {
+        for c in 0..4 {
+            let error = (pa[c] as f64 - pb[c] as f64).abs();
+            max_error[c] = max_error[c].max(error);
+            squared += error * error;
+        }
+    }
+    let mse = squared / a.len().max(1) as f64;
+    let peak = a
+        .chunks_exact(4)
+        .flat_map(|p| &p[..3])
+        .fold(1.0f64, |peak, &c| peak.max(c as f64));
+    ImageDiff {
+        mse,
+        psnr: if mse > 0.0 {
+            10.0 * (peak * peak / mse).log10()
+        } else {
+            f64::INFINITY
+        },
+        ssim: ssim(&luma(a), &luma(b), width as usize, height as usize, peak),
+        max_error,
+    }
+}
+
+/// Returns an image showing where two RGBA32F images of the same size differ, by the largest
+/// channel difference of each pixel: black where they match, through red and yellow, to white at
+/// `full_scale`.
+pub fn diff_heatmap(a: &[f32], b: &[f32], width: u32, height: u32, full_scale: f32) -> Image {
+    let full_scale = if full_scale > 0.0 { full_scale } else { 1.0 };
+    Image {
+        width,
+        height,
+        pixels: a
+            .chunks_exact(4)
+            .zip(b.chunks_exact(4))
+            .map(|(pa, pb)| {
+                let error = (0..4).map(|c| (pa[c] - pb[c]).abs()).fold(0.0, f32::max);
+                let t = (error / full_scale).min(1.0) * 3.0;
+                let ramp = [t, t - 1.0, t - 2.0].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+                [ramp[0], ramp[1], ramp[2], 255]
+            })
+            .collect(),
+    }
+}
+
+/// Returns the Rec. 709 luma of each pixel, composited over black.
+fn luma(pixels: &[f32]) -> Vec<f64> {
+    pixels
+        .chunks_exact(4)
+        .map(|p| {
+            let [r, g, b, a] = [p[0], p[1], p[2], p[3]].map(|c| c as f64);
+            (0.2126 * r + 0.7152 * g + 0.0722 * b) * a.clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+/// Returns the mean SSIM of two luma planes with values up to `peak`, with statistics gathered
+/// over a Gaussian window around each pixel that is cut off at the edges.
+fn ssim(a: &[f64], b: &[f64], width: usize, height: usize, peak: f64) -> f64 {
+    if a.is_empty() {
+        return 1.0;
+    }
+    let c1 = (SSIM_K1 * peak).powi(2);
+    let c2 = (SSIM_K2 * peak).powi(2);
+    let weights: Vec<f64> = (-SSIM_RADIUS..=SSIM_RADIUS)
+        .map(|i| (-(i * i) as f64 / (2.0 * SSIM_SIGMA * SSIM_SIGMA)).exp())
+        .collect();
+    let planes = [
+        a.to_vec(),
+        b.to_vec(),
+        a.iter().map(|v| v * v).collect(),
+        b.iter().map(|v| v * v).collect(),
+        a.iter().zip(b).map(|(x, y)| x * y).collect(),
+    ];
+    let ones = vec![1.0; a.len()];
+    let total = blur(&ones, &weights, width, height);
+    let [mean_a, mean_b, mean_aa, mean_bb, mean_ab] =
+        planes.map(|plane| blur(&plane, &weights, width, height));
+
+    let mut sum = 0.0;
+    for i in 0..a.len() {
+        let (ma, mb) = (mean_a[i] / total[i], mean_b[i] / total[i]);
+        let var_a = mean_aa[i] / total[i] - ma * ma;
+        let var_b = mean_bb[i] / total[i] - mb * mb;
+        let covariance = mean_ab[i] / total[i] - ma * mb;
+        sum += ((2.0 * ma * mb + c1) * (2.0 * covariance + c2))
+            / ((ma * ma + mb * mb + c1) * (var_a + var_b + c2));
+    }
+    sum / a.len() as f64
+}
+
+/// Returns the weighted sums of `plane` over a separable window, leaving out texels past the
+/// edges.
+fn blur(plane: &[f64], weights: &[f64], width: usize, height: usize) -> Vec<f64> {
+    let radius = (weights.len() / 2) as i64;
+    let mut rows = vec![0.0; plane.len()];
+    for y in 0..height {
+        for x in 0..width {
+            rows[y * width + x] = (-radius..=radius)
+                .zip(weights)
+                .filter_map(|(i, w)| {
+                    let sx = x as i64 + i;
+                    (0..width as i64)
+                        .contains(&sx)
+                        .then(|| w * plane[y * width + sx as usize])
+                })
+                .sum();
+        }
+    }
+    let mut out = vec![0.0; plane.len()];
+    for y in 0..height {
+        for x in 0..width {
+            out[y * width + x] = (-radius..=radius)
+                .zip(weights)
+                .filter_map(|(i, w)| {
+                    let sy = y as i64 + i;
+                    (0..height as i64)
+                        .contains(&sy)
+                        .then(|| w * rows[sy as usize * width + x])
+                })
+                .sum();
+        }
+    }
+    out
+}
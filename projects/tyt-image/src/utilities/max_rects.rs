@@ -0,0 +1,136 @@
+use crate::AtlasRect;
+
+/// A rectangle bin packer using the MaxRects algorithm with the best-short-side-fit heuristic.
+///
+/// The bin tracks every maximal free rectangle, so a new rectangle can go into any gap large
+/// enough to hold it.
+#[derive(Clone, Debug)]
+pub struct MaxRects {
+    free: Vec<AtlasRect>,
+}
+
+impl MaxRects {
+    /// Creates an empty `width` by `height` bin.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            free: vec![AtlasRect {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            }],
+        }
+    }
+
+    /// Places a `width` by `height` rectangle, turned a quarter turn when `allow_rotation` is set
+    /// and that fits better. Returns where it went and whether it was turned, or `None` when it
+    /// doesn't fit.
+    pub fn insert(
+        &mut self,
+        width: u32,
+        height: u32,
+        allow_rotation: bool,
+    ) -> Option<(AtlasRect, bool)> {
+        let mut best: Option<(AtlasRect, bool, (u32, u32))> = None;
+        let orientations: &[bool] = if allow_rotation && width != height {
+            &[false, true]
+        } else {
+            &[false]
+        };
+        for free in &self.free {
+            for &rotated in orientations {
+                let (w, h) = if rotated {
+                    (height, width)
+                } else {
+                    (width, height)
+                };
+                if w > free.width || h > free.height {
+                    continue;
+                }
+                let (dx, dy) = (free.width - w, free.height - h);
+                let score = (dx.min(dy), dx.max(dy));
+                if best.is_none_or(|(_, _, best_score)| score < best_score) {
+                    let rect = AtlasRect {
+                        x: free.x,
+                        y: free.y,
+                        width: w,
+                        height: h,
+                    };
+                    best = Some((rect, rotated, score));
+                }
+            }
+        }
+        let (rect, rotated, _) = best?;
+        self.place(rect);
+        Some((rect, rotated))
+    }
+
+    /// Removes `used` from the free rectangles, splitting each one it overlaps into the
+    /// maximal rectangles left around it.
+    fn place(&mut self, used: AtlasRect) {
+        let mut next = Vec::with_capacity(self.free.len() + 4);
+        for free in &self.free {
+            if !intersects(free, &used) {
+                next.push(*free);
+                continue;
+            }
+            if used.x > free.x {
+                next.push(AtlasRect {
+                    width: used.x - free.x,
+                    ..*free
+                });
+            }
+            if right(&used) < right(free) {
+                next.push(AtlasRect {
+                    x: right(&used),
+                    width: right(free) - right(&used),
+                    ..*free
+                });
+            }
+            if used.y > free.y {
+                next.push(AtlasRect {
+                    height: used.y - free.y,
+                    ..*free
+                });
+            }
+            if bottom(&used) < bottom(free) {
+                next.push(AtlasRect {
+                    y: bottom(&used),
+                    height: bottom(free) - bottom(&used),
+                    ..*free
+                });
+            }
+        }
+        // Drop rectangles that lie inside others.
+        let mut pruned: Vec<AtlasRect> = Vec::with_capacity(next.len());
+        for (i, rect) in next.iter().enumerate() {
+            let redundant = next
+                .iter()
+                .enumerate()
+                .any(|(j, other)| i != j && contains(other, rect) && (other != rect || j < i));
+            if !redundant {
+                pruned.push(*rect);
+            }
+        }
+        self.free = pruned;
+    }
+}
+
+fn right(rect: &AtlasRect) -> u32 {
+    rect.x + rect.width
+}
+
+fn bottom(rect: &AtlasRect) -> u32 {
+    rect.y + rect.height
+}
+
+fn contains(outer: &AtlasRect, inner: &AtlasRect) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && right(inner) <= right(outer)
+        && bottom(inner) <= bottom(outer)
+}
+
+fn intersects(a: &AtlasRect, b: &AtlasRect) -> bool {
+    a.x < right(b) && b.x < right(a) && a.y < bottom(b) && b.y < bottom(a)
+}
@@ -0,0 +1,44 @@
+use crate::utilities::Image;
+use clap::ValueEnum;
+use tyt_common::{TextureFormat, TextureLevels, encode_dds, encode_ktx2};
+
+/// A single-file GPU texture container holding a whole mip chain.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum MipContainer {
+    /// Khronos KTX2, as used by Vulkan, OpenGL, and glTF tooling.
+    #[default]
+    Ktx2,
+
+    /// DirectDraw Surface with a DX10 header, as used by Direct3D.
+    Dds,
+}
+
+impl MipContainer {
+    /// Returns the file extension for this container, without a leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            MipContainer::Ktx2 => "ktx2",
+            MipContainer::Dds => "dds",
+        }
+    }
+
+    /// Encodes a mip chain with `mips[0]` at full resolution, marking the texels as
+    /// sRGB-encoded when `srgb` is set.
+    pub fn encode(self, mips: &[Image], srgb: bool) -> Vec<u8> {
+        let texture = TextureLevels {
+            format: if srgb {
+                TextureFormat::Rgba8Srgb
+            } else {
+                TextureFormat::Rgba8Unorm
+            },
+            width: mips[0].width,
+            height: mips[0].height,
+            faces: 1,
+            levels: mips.iter().map(Image::to_rgba8).collect(),
+        };
+        match self {
+            MipContainer::Ktx2 => encode_ktx2(&texture),
+            MipContainer::Dds => encode_dds(&texture),
+        }
+    }
+}
@@ -0,0 +1,402 @@
+use crate::utilities::{Image, NormalConvention, decode_normal, encode_normal, normalize};
+use clap::ValueEnum;
+use std::f32::consts::PI;
+use tyt_common::{linear_to_srgb, srgb_to_linear};
+
+/// The filter used to shrink each mip level from the one above.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum MipFilter {
+    /// Average each 2x2 block. Fast, but slightly blurry and prone to aliasing.
+    Box,
+
+    /// Kaiser-windowed sinc, sharp with little ringing.
+    #[default]
+    Kaiser,
+
+    /// Lanczos-3 windowed sinc, the sharpest, with some ringing at hard edges.
+    Lanczos,
+}
+
+impl MipFilter {
+    /// Returns the filter's radius in source pixels at a scale of 1.
+    fn radius(self) -> f32 {
+        match self {
+            MipFilter::Box => 0.5,
+            MipFilter::Kaiser | MipFilter::Lanczos => 3.0,
+        }
+    }
+
+    /// Returns the filter's weight at `x` source pixels from the center, at a scale of 1.
+    fn weight(self, x: f32) -> f32 {
+        let x = x.abs();
+        match self {
+            MipFilter::Box => {
+                if x <= 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            MipFilter::Kaiser => {
+                let t = x / self.radius();
+                if t >= 1.0 {
+                    0.0
+                } else {
+                    sinc(x) * bessel_i0(KAISER_ALPHA * (1.0 - t * t).sqrt())
+                        / bessel_i0(KAISER_ALPHA)
+                }
+            }
+            MipFilter::Lanczos => {
+                if x >= self.radius() {
+                    0.0
+                } else {
+                    sinc(x) * sinc(x / self.radius())
+                }
+            }
+        }
+    }
+}
+
+/// What a texture's channels hold, which decides how they're filtered.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum TextureKind {
+    /// sRGB color, filtered in linear light and weighted by alpha so transparent texels don't
+    /// bleed in.
+    #[default]
+    Color,
+
+    /// Non-color data such as roughness or masks, filtered as stored.
+    Data,
+
+    /// Tangent-space normals, filtered as vectors and renormalized at every level.
+    Normal,
+}
+
+/// How to bring an image to power-of-two dimensions before building mips.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum PowerOfTwo {
+    /// Extend the right and bottom edges out to the next power of two.
+    Pad,
+
+    /// Resample to the nearest power of two on each side.
+    Resize,
+}
+
+/// Options for building a mip chain.
+#[derive(Clone, Copy, Debug)]
+pub struct MipOptions {
+    pub filter: MipFilter,
+    pub kind: TextureKind,
+
+    /// Alpha test cutoff in `[0, 1]`. When set, each level's alpha is scaled so the same share
+    /// of texels passes the test as in the top level, keeping cutouts from thinning out.
+    pub alpha_cutoff: Option<f32>,
+
+    /// Wrap around the edges when filtering, for tiling textures.
+    pub wrap: bool,
+}
+
+/// Shape parameter of the Kaiser window; higher trades sharpness for less ringing.
+const KAISER_ALPHA: f32 = 4.0;
+
+/// Steps of the search for each level's alpha scale.
+const COVERAGE_SEARCH_STEPS: u32 = 16;
+
+/// Largest alpha scale tried when preserving coverage.
+const MAX_ALPHA_SCALE: f32 = 4.0;
+
+/// A working image of four float channels.
+#[derive(Clone, Debug)]
+struct Plane {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 4]>,
+
+    /// For color, the linear color filtered without alpha weighting, which stands in for texels
+    /// that end up fully transparent and so have no premultiplied color left. Empty otherwise.
+    straight: Vec<[f32; 3]>,
+}
+
+/// Builds the full mip chain of `image`, from the image itself down to 1x1, halving each side
+/// per level and rounding down.
+pub fn generate_mipmaps(image: &Image, options: MipOptions) -> Vec<Image> {
+    let top = decode(image, options.kind);
+    let coverage = options
+        .alpha_cutoff
+        .map(|cutoff| (cutoff, alpha_coverage(&top.pixels, cutoff, 1.0)));
+
+    let mut levels = vec![image.clone()];
+    let mut current = top;
+    while current.width > 1 || current.height > 1 {
+        let width = (current.width / 2).max(1);
+        let height = (current.height / 2).max(1);
+        current = resample(&current, width, height, options.filter, options.wrap);
+        let mut level = current.clone();
+        if let Some((cutoff, coverage)) = coverage {
+            scale_alpha_to_coverage(&mut level, cutoff, coverage, options.kind);
+        }
+        levels.push(encode(&level, options.kind));
+    }
+    levels
+}
+
+/// Returns `image` extended to the next power of two on each side by repeating its right and
+/// bottom edges.
+pub fn pad_to_power_of_two(image: &Image) -> Image {
+    let width = image.width.next_power_of_two();
+    let height = image.height.next_power_of_two();
+    let mut out = Image::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            out.set(
+                x,
+                y,
+                image.get(x.min(image.width - 1), y.min(image.height - 1)),
+            );
+        }
+    }
+    out
+}
+
+/// Returns `image` resampled to the nearest power of two on each side.
+pub fn resize_to_power_of_two(image: &Image, options: MipOptions) -> Image {
+    let nearest = |size: u32| {
+        let above = size.next_power_of_two();
+        if above - size <= size - above / 2 {
+            above
+        } else {
+            above / 2
+        }
+    };
+    let (width, height) = (nearest(image.width), nearest(image.height));
+    if (width, height) == (image.width, image.height) {
+        return image.clone();
+    }
+    resize_texture(image, width, height, options)
+}
+
+/// Returns `image` resampled to `width` by `height` with the filtering of `options`, widening
+/// the filter when shrinking so every source texel contributes.
+pub fn resize_texture(image: &Image, width: u32, height: u32, options: MipOptions) -> Image {
+    let plane = decode(image, options.kind);
+    encode(
+        &resample(&plane, width, height, options.filter, options.wrap),
+        options.kind,
+    )
+}
+
+/// Converts an image to working values: premultiplied linear light for color, unit vectors for
+/// normals, and `[0, 1]` channels for data.
+fn decode(image: &Image, kind: TextureKind) -> Plane {
+    let linear = |p: [u8; 4]| [0, 1, 2].map(|c| srgb_to_linear(p[c] as f32 / 255.0));
+    Plane {
+        width: image.width,
+        height: image.height,
+        pixels: image
+            .pixels
+            .iter()
+            .map(|&p| {
+                let alpha = p[3] as f32 / 255.0;
+                match kind {
+                    TextureKind::Color => {
+                        let [r, g, b] = linear(p);
+                        [r * alpha, g * alpha, b * alpha, alpha]
+                    }
+                    TextureKind::Data => p.map(|c| c as f32 / 255.0),
+                    TextureKind::Normal => {
+                        let [x, y, z] = decode_normal(p, NormalConvention::OpenGl);
+                        [x, y, z, alpha]
+                    }
+                }
+            })
+            .collect(),
+        straight: match kind {
+            TextureKind::Color => image.pixels.iter().map(|&p| linear(p)).collect(),
+            TextureKind::Data | TextureKind::Normal => Vec::new(),
+        },
+    }
+}
+
+/// Converts working values back to an 8-bit image, the inverse of [`decode`].
+fn encode(plane: &Plane, kind: TextureKind) -> Image {
+    let to_u8 = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Image {
+        width: plane.width,
+        height: plane.height,
+        pixels: plane
+            .pixels
+            .iter()
+            .enumerate()
+            .map(|(i, &[r, g, b, a])| match kind {
+                TextureKind::Color => {
+                    let color = if to_u8(a) == 0 {
+                        plane.straight[i]
+                    } else {
+                        [r / a, g / a, b / a]
+                    };
+                    let [r, g, b] = color.map(|c| to_u8(linear_to_srgb(c.clamp(0.0, 1.0))));
+                    [r, g, b, to_u8(a)]
+                }
+                TextureKind::Data => [r, g, b, a].map(to_u8),
+                TextureKind::Normal => {
+                    let [r, g, b, _] =
+                        encode_normal(normalize([r, g, b]), NormalConvention::OpenGl);
+                    [r, g, b, to_u8(a)]
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Resamples a plane to `width` by `height`, filtering rows then columns.
+fn resample(plane: &Plane, width: u32, height: u32, filter: MipFilter, wrap: bool) -> Plane {
+    let columns = weights(plane.width, width, filter, wrap);
+    let rows = weights(plane.height, height, filter, wrap);
+    Plane {
+        width,
+        height,
+        pixels: filter_separable(&plane.pixels, plane.width, &columns, &rows),
+        straight: if plane.straight.is_empty() {
+            Vec::new()
+        } else {
+            filter_separable(&plane.straight, plane.width, &columns, &rows)
+        },
+    }
+}
+
+/// Filters `pixels`, rows `src_width` long, with `columns` taps along each row and then `rows`
+/// taps down each column.
+fn filter_separable<const N: usize>(
+    pixels: &[[f32; N]],
+    src_width: u32,
+    columns: &[Vec<(usize, f32)>],
+    rows: &[Vec<(usize, f32)>],
+) -> Vec<[f32; N]> {
+    let (src_width, width) = ((src_width as usize).max(1), columns.len());
+    let mut horizontal = Vec::with_capacity(width * pixels.len() / src_width);
+    for src in pixels.chunks_exact(src_width) {
+        for taps in columns {
+            horizontal.push(weighted_sum(taps.iter().map(|&(i, w)| (src[i], w))));
+        }
+    }
+    let mut out = Vec::with_capacity(width * rows.len());
+    for taps in rows {
+        for x in 0..width {
+            out.push(weighted_sum(
+                taps.iter().map(|&(i, w)| (horizontal[i * width + x], w)),
+            ));
+        }
+    }
+    out
+}
+
+/// Returns, for each destination index along one axis, the source indices and normalized
+/// weights that contribute to it.
+fn weights(src: u32, dst: u32, filter: MipFilter, wrap: bool) -> Vec<Vec<(usize, f32)>> {
+    // Widen the filter when shrinking so it covers every source pixel it stands for.
+    let scale = (src as f32 / dst as f32).max(1.0);
+    let support = filter.radius() * scale;
+    (0..dst)
+        .map(|d| {
+            let center = (d as f32 + 0.5) * src as f32 / dst as f32;
+            let first = (center - support).floor() as i64;
+            let last = (center + support).ceil() as i64;
+            let mut taps: Vec<(usize, f32)> = Vec::new();
+            for s in first..=last {
+                let weight = filter.weight((s as f32 + 0.5 - center) / scale);
+                if weight == 0.0 {
+                    continue;
+                }
+                let index = if wrap {
+                    s.rem_euclid(src as i64)
+                } else {
+                    s.clamp(0, src as i64 - 1)
+                } as usize;
+                match taps.iter_mut().find(|(i, _)| *i == index) {
+                    Some((_, w)) => *w += weight,
+                    None => taps.push((index, weight)),
+                }
+            }
+            let total: f32 = taps.iter().map(|&(_, w)| w).sum();
+            if total.abs() < f32::EPSILON {
+                // Nothing landed inside the filter; take the nearest source pixel.
+                let nearest = (center as i64).clamp(0, src as i64 - 1) as usize;
+                return vec![(nearest, 1.0)];
+            }
+            taps.iter().map(|&(i, w)| (i, w / total)).collect()
+        })
+        .collect()
+}
+
+fn weighted_sum<const N: usize>(taps: impl Iterator<Item = ([f32; N], f32)>) -> [f32; N] {
+    let mut sum = [0.0; N];
+    for (value, weight) in taps {
+        for c in 0..N {
+            sum[c] += value[c] * weight;
+        }
+    }
+    sum
+}
+
+/// Returns the share of texels whose alpha, multiplied by `scale`, reaches `cutoff`.
+fn alpha_coverage(pixels: &[[f32; 4]], cutoff: f32, scale: f32) -> f32 {
+    let passing = pixels.iter().filter(|p| p[3] * scale >= cutoff).count();
+    passing as f32 / pixels.len().max(1) as f32
+}
+
+/// Scales a level's alpha so the share of texels passing `cutoff` matches `coverage`,
+/// rescaling premultiplied color along with it.
+fn scale_alpha_to_coverage(plane: &mut Plane, cutoff: f32, coverage: f32, kind: TextureKind) {
+    let (mut low, mut high) = (0.0f32, MAX_ALPHA_SCALE);
+    for _ in 0..COVERAGE_SEARCH_STEPS {
+        let mid = 0.5 * (low + high);
+        if alpha_coverage(&plane.pixels, cutoff, mid) < coverage {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    // Coverage moves in steps, so take whichever end of the bracket lands closest.
+    let scale = [low, high]
+        .into_iter()
+        .min_by(|&a, &b| {
+            let miss = |scale| (alpha_coverage(&plane.pixels, cutoff, scale) - coverage).abs();
+            miss(a).total_cmp(&miss(b))
+        })
+        .unwrap_or(1.0);
+    for pixel in &mut plane.pixels {
+        let alpha = (pixel[3] * scale).min(1.0);
+        if kind == TextureKind::Color {
+            // Keep the unpremultiplied color the same at the new alpha.
+            let ratio = if pixel[3] > 0.0 {
+                alpha / pixel[3]
+            } else {
+                0.0
+            };
+            for channel in &mut pixel[..3] {
+                *channel *= ratio;
+            }
+        }
+        pixel[3] = alpha;
+    }
+}
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-6 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+/// Zeroth-order modified Bessel function of the first kind, by its power series.
+fn bessel_i0(x: f32) -> f32 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half = x / 2.0;
+    for k in 1..20 {
+        term *= (half / k as f32) * (half / k as f32);
+        sum += term;
+    }
+    sum
+}
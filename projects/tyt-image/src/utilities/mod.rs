@@ -0,0 +1,31 @@
+mod atlas;
+mod backend;
+mod dilate;
+mod dither;
+mod height_field;
+mod image;
+mod image_diff;
+mod max_rects;
+mod mip_container;
+mod mipmaps;
+mod normal_map;
+mod palette_generation;
+mod resize;
+mod slice;
+mod square;
+
+pub use atlas::*;
+pub use backend::*;
+pub use dilate::*;
+pub use dither::*;
+pub use height_field::*;
+pub use image::*;
+pub use image_diff::*;
+pub use max_rects::*;
+pub use mip_container::*;
+pub use mipmaps::*;
+pub use normal_map::*;
+pub use palette_generation::*;
+pub use resize::*;
+pub use slice::*;
+pub use square::*;
@@ -0,0 +1,232 @@
+use crate::utilities::{HeightField, Image};
+use clap::ValueEnum;
+
+/// Which way the green channel of a tangent-space normal map points.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum NormalConvention {
+    /// Green points up the texture (Y+), as in OpenGL, Blender, Unity, and Godot.
+    #[default]
+    #[value(name = "opengl")]
+    OpenGl,
+
+    /// Green points down the texture (Y-), as in DirectX and Unreal.
+    #[value(name = "directx")]
+    DirectX,
+}
+
+/// The derivative filter used to measure slopes in a heightmap.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum GradientKernel {
+    /// 3x3 Sobel, weighting the center row 2:1.
+    #[default]
+    Sobel,
+
+    /// 3x3 Scharr, weighting the center row 10:3 for better rotational accuracy.
+    Scharr,
+}
+
+impl GradientKernel {
+    /// Returns the weights of the rows above, through, and below the center.
+    fn weights(self) -> [f32; 3] {
+        match self {
+            GradientKernel::Sobel => [1.0, 2.0, 1.0],
+            GradientKernel::Scharr => [3.0, 10.0, 3.0],
+        }
+    }
+}
+
+/// Smallest Z kept when turning normals into slopes, so near-horizontal normals don't produce
+/// huge slopes.
+const MIN_NORMAL_Z: f32 = 0.1;
+
+/// Gauss-Seidel passes made at each level when integrating slopes into heights.
+const INTEGRATION_PASSES: u32 = 40;
+
+/// Levels no larger than this on either side are solved directly.
+const COARSEST_SIZE: u32 = 4;
+
+/// Decodes a normal map pixel into a unit vector in the OpenGL convention.
+pub fn decode_normal(pixel: [u8; 4], convention: NormalConvention) -> [f32; 3] {
+    let [x, mut y, z] = [0, 1, 2].map(|c| pixel[c] as f32 / 255.0 * 2.0 - 1.0);
+    if convention == NormalConvention::DirectX {
+        y = -y;
+    }
+    normalize([x, y, z])
+}
+
+/// Encodes a unit vector in the OpenGL convention as an opaque normal map pixel.
+pub fn encode_normal(normal: [f32; 3], convention: NormalConvention) -> [u8; 4] {
+    let [x, mut y, z] = normal;
+    if convention == NormalConvention::DirectX {
+        y = -y;
+    }
+    let [r, g, b] = [x, y, z].map(|c| ((c * 0.5 + 0.5).clamp(0.0, 1.0) * 255.0).round() as u8);
+    [r, g, b, 255]
+}
+
+/// Returns `v` scaled to unit length, or straight up for a zero vector.
+pub fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let length = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if length > 0.0 {
+        v.map(|c| c / length)
+    } else {
+        [0.0, 0.0, 1.0]
+    }
+}
+
+/// Builds a tangent-space normal map from a heightmap, where brighter is higher.
+///
+/// Slopes are measured with `kernel` in height units per pixel and multiplied by `strength`.
+/// Edges wrap around when `wrap` is set, for tiling textures.
+pub fn normals_from_height(
+    heights: &HeightField,
+    strength: f32,
+    kernel: GradientKernel,
+    wrap: bool,
+    convention: NormalConvention,
+) -> Image {
+    let weights = kernel.weights();
+    let total: f32 = weights.iter().sum();
+    let mut image = Image::new(heights.width, heights.height);
+    for y in 0..heights.height {
+        for x in 0..heights.width {
+            let (x, y) = (x as i64, y as i64);
+            let mut dx = 0.0;
+            let mut dy = 0.0;
+            for (i, weight) in (-1..=1).zip(weights) {
+                dx += weight * (heights.get(x + 1, y + i, wrap) - heights.get(x - 1, y + i, wrap));
+                dy += weight * (heights.get(x + i, y + 1, wrap) - heights.get(x + i, y - 1, wrap));
+            }
+            // Central differences span two pixels.
+            let (dx, dy) = (dx / (2.0 * total) * strength, dy / (2.0 * total) * strength);
+            // Image rows run down while the normal's Y points up the texture.
+            let normal = normalize([-dx, dy, 1.0]);
+            image.set(x as u32, y as u32, encode_normal(normal, convention));
+        }
+    }
+    image
+}
+
+/// Returns a normal map with its green channel inverted, converting between the OpenGL and
+/// DirectX conventions.
+pub fn flip_green(image: &Image) -> Image {
+    let mut out = image.clone();
+    for pixel in &mut out.pixels {
+        pixel[1] = 255 - pixel[1];
+    }
+    out
+}
+
+/// Returns a normal map with every vector rescaled to unit length, as needed after resizing or
+/// blending, keeping alpha.
+pub fn renormalize(image: &Image) -> Image {
+    let mut out = image.clone();
+    for pixel in &mut out.pixels {
+        let alpha = pixel[3];
+        *pixel = encode_normal(
+            decode_normal(*pixel, NormalConvention::OpenGl),
+            NormalConvention::OpenGl,
+        );
+        pixel[3] = alpha;
+    }
+    out
+}
+
+/// Reconstructs a heightmap from a normal map by finding the heights whose slopes best match
+/// the normals, in height units per pixel.
+///
+/// The least-squares fit is solved coarse to fine: each level starts from the upsampled
+/// solution of a half-size level and is refined with Gauss-Seidel passes, which converges in
+/// far fewer passes than solving at full size alone.
+pub fn height_from_normals(image: &Image, convention: NormalConvention, wrap: bool) -> HeightField {
+    let mut slope_x = Vec::with_capacity(image.pixels.len());
+    let mut slope_y = Vec::with_capacity(image.pixels.len());
+    for &pixel in &image.pixels {
+        let [nx, ny, nz] = decode_normal(pixel, convention);
+        let nz = nz.max(MIN_NORMAL_Z);
+        // Inverse of `normals_from_height`: the normal is proportional to (-dx, dy, 1) with Y
+        // down the image.
+        slope_x.push(-nx / nz);
+        slope_y.push(ny / nz);
+    }
+    let values = integrate(&slope_x, &slope_y, image.width, image.height, wrap);
+    HeightField {
+        width: image.width,
+        height: image.height,
+        values,
+    }
+}
+
+/// Returns heights whose differences between neighbors match the slopes, which give the
+/// height change per pixel toward `+x` and `+y` (down the image).
+fn integrate(slope_x: &[f32], slope_y: &[f32], width: u32, height: u32, wrap: bool) -> Vec<f32> {
+    let (w, h) = (width as usize, height as usize);
+    let mut heights = if width <= COARSEST_SIZE || height <= COARSEST_SIZE {
+        vec![0.0; w * h]
+    } else {
+        // Average slopes over 2x2 blocks, doubling them since each coarse pixel spans two.
+        let (cw, ch) = (width.div_ceil(2), height.div_ceil(2));
+        let mut coarse_x = vec![0.0; (cw * ch) as usize];
+        let mut coarse_y = vec![0.0; (cw * ch) as usize];
+        let mut counts = vec![0.0f32; (cw * ch) as usize];
+        for y in 0..h {
+            for x in 0..w {
+                let coarse = (y / 2) * cw as usize + x / 2;
+                coarse_x[coarse] += slope_x[y * w + x];
+                coarse_y[coarse] += slope_y[y * w + x];
+                counts[coarse] += 1.0;
+            }
+        }
+        for ((sx, sy), count) in coarse_x.iter_mut().zip(&mut coarse_y).zip(&counts) {
+            *sx *= 2.0 / count;
+            *sy *= 2.0 / count;
+        }
+        let coarse = integrate(&coarse_x, &coarse_y, cw, ch, wrap);
+        (0..h)
+            .flat_map(|y| (0..w).map(move |x| (x, y)))
+            .map(|(x, y)| coarse[(y / 2) * cw as usize + x / 2])
+            .collect()
+    };
+
+    let passes = if width <= COARSEST_SIZE || height <= COARSEST_SIZE {
+        INTEGRATION_PASSES * 10
+    } else {
+        INTEGRATION_PASSES
+    };
+    let neighbor = |x: usize, y: usize, dx: i64, dy: i64| -> Option<usize> {
+        let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+        if wrap {
+            Some(ny.rem_euclid(h as i64) as usize * w + nx.rem_euclid(w as i64) as usize)
+        } else if nx < 0 || ny < 0 || nx >= w as i64 || ny >= h as i64 {
+            None
+        } else {
+            Some(ny as usize * w + nx as usize)
+        }
+    };
+    for _ in 0..passes {
+        for y in 0..h {
+            for x in 0..w {
+                let here = y * w + x;
+                let mut sum = 0.0;
+                let mut count = 0.0;
+                for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                    let Some(there) = neighbor(x, y, dx, dy) else {
+                        continue;
+                    };
+                    // The expected rise from here to there, averaging both ends' slopes.
+                    let rise = if dx != 0 {
+                        dx as f32 * 0.5 * (slope_x[here] + slope_x[there])
+                    } else {
+                        dy as f32 * 0.5 * (slope_y[here] + slope_y[there])
+                    };
+                    sum += heights[there] - rise;
+                    count += 1.0;
+                }
+                if count > 0.0 {
+                    heights[here] = sum / count;
+                }
+            }
+        }
+    }
+    heights
+}
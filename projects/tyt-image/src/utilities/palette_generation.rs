@@ -0,0 +1,180 @@
+use crate::{Result, utilities::Image};
+use clap::ValueEnum;
+use std::collections::HashMap;
+use tyt_common::{
+    Palette, distance_squared, invalid_data, linear_to_oklab, linear_to_srgb8, srgb8_to_linear,
+};
+
+/// Most refinement passes k-means makes before settling for its current palette.
+const KMEANS_ITERATIONS: u32 = 16;
+
+/// How a palette is chosen from an image's own colors.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum PaletteMethod {
+    /// Repeatedly split the box of colors with the widest spread at its median.
+    MedianCut,
+
+    /// Start from median cut, then move each color to the mean of the pixels nearest it until
+    /// the palette settles. Slower, with a closer fit.
+    #[default]
+    KMeans,
+}
+
+/// A distinct color of an image with its pixel count.
+struct Sample {
+    linear: [f32; 3],
+    oklab: [f32; 3],
+    count: u32,
+}
+
+/// Chooses up to `count` colors that represent the opaque pixels of `image`, comparing colors
+/// in Oklab and averaging them in linear light.
+pub fn generate_palette(image: &Image, count: usize, method: PaletteMethod) -> Result<Palette> {
+    let mut counts: HashMap<[u8; 3], u32> = HashMap::new();
+    for &[r, g, b, a] in &image.pixels {
+        if a > 0 {
+            *counts.entry([r, g, b]).or_default() += 1;
+        }
+    }
+    if counts.is_empty() {
+        return Err(invalid_data(
+            "the image has no opaque pixels to choose colors from",
+        ));
+    }
+    let mut samples: Vec<Sample> = counts
+        .into_iter()
+        .map(|(color, count)| {
+            let linear = srgb8_to_linear(color);
+            Sample {
+                linear,
+                oklab: linear_to_oklab(linear),
+                count,
+            }
+        })
+        .collect();
+    // Sort so the result doesn't depend on hash order.
+    samples.sort_by(|a, b| {
+        a.oklab
+            .partial_cmp(&b.oklab)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut centers = median_cut(&mut samples, count.max(1));
+    if method == PaletteMethod::KMeans {
+        centers = kmeans(&samples, centers);
+    }
+    let mut colors: Vec<[u8; 3]> = Vec::new();
+    for center in centers {
+        let color = linear_to_srgb8(center);
+        if !colors.contains(&color) {
+            colors.push(color);
+        }
+    }
+    Ok(Palette::new(colors))
+}
+
+/// Splits `samples` into up to `count` boxes, returning the mean linear color of each.
+fn median_cut(samples: &mut [Sample], count: usize) -> Vec<[f32; 3]> {
+    let mut boxes = Vec::with_capacity(count);
+    boxes.push(0..samples.len());
+    while boxes.len() < count {
+        // Split the box whose widest Oklab axis is widest overall.
+        let Some((index, axis, _)) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, range)| range.len() > 1)
+            .map(|(i, range)| {
+                let (axis, extent) = widest_axis(&samples[range.clone()]);
+                (i, axis, extent)
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+        else {
+            break;
+        };
+        let range = boxes.swap_remove(index);
+        let slice = &mut samples[range.clone()];
+        slice.sort_by(|a, b| a.oklab[axis].total_cmp(&b.oklab[axis]));
+
+        // Split where half the pixels, rather than half the distinct colors, fall on each side.
+        let total: u64 = slice.iter().map(|s| s.count as u64).sum();
+        let mut seen = 0;
+        let mut split = 1;
+        for (i, sample) in slice.iter().enumerate() {
+            seen += sample.count as u64;
+            if 2 * seen >= total {
+                split = (i + 1).clamp(1, slice.len() - 1);
+                break;
+            }
+        }
+        boxes.push(range.start..range.start + split);
+        boxes.push(range.start + split..range.end);
+    }
+    boxes
+        .into_iter()
+        .map(|range| mean_linear(&samples[range]))
+        .collect()
+}
+
+/// Returns the Oklab axis along which `samples` spread the most, and that spread.
+fn widest_axis(samples: &[Sample]) -> (usize, f32) {
+    (0..3)
+        .map(|axis| {
+            let (min, max) = samples.iter().fold((f32::MAX, f32::MIN), |(min, max), s| {
+                (min.min(s.oklab[axis]), max.max(s.oklab[axis]))
+            });
+            (axis, max - min)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, 0.0))
+}
+
+/// Returns the pixel-weighted mean linear color of `samples`.
+fn mean_linear(samples: &[Sample]) -> [f32; 3] {
+    let mut sum = [0.0f64; 3];
+    let mut total = 0.0f64;
+    for sample in samples {
+        for (sum, channel) in sum.iter_mut().zip(sample.linear) {
+            *sum += channel as f64 * sample.count as f64;
+        }
+        total += sample.count as f64;
+    }
+    sum.map(|c| (c / total.max(1.0)) as f32)
+}
+
+/// Refines linear-light `centers` with Lloyd's algorithm, assigning samples in Oklab.
+fn kmeans(samples: &[Sample], mut centers: Vec<[f32; 3]>) -> Vec<[f32; 3]> {
+    let mut assignment = vec![usize::MAX; samples.len()];
+    for _ in 0..KMEANS_ITERATIONS {
+        let oklab: Vec<[f32; 3]> = centers.iter().map(|&c| linear_to_oklab(c)).collect();
+        let mut changed = false;
+        for (sample, assigned) in samples.iter().zip(&mut assignment) {
+            let nearest = (0..oklab.len())
+                .min_by(|&a, &b| {
+                    distance_squared(sample.oklab, oklab[a])
+                        .total_cmp(&distance_squared(sample.oklab, oklab[b]))
+                })
+                .unwrap_or(0);
+            changed |= *assigned != nearest;
+            *assigned = nearest;
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![([0.0f64; 3], 0.0f64); centers.len()];
+        for (sample, &assigned) in samples.iter().zip(&assignment) {
+            let (sum, total) = &mut sums[assigned];
+            for (sum, channel) in sum.iter_mut().zip(sample.linear) {
+                *sum += channel as f64 * sample.count as f64;
+            }
+            *total += sample.count as f64;
+        }
+        for (center, (sum, total)) in centers.iter_mut().zip(sums) {
+            // Centers that lost all their samples keep their place.
+            if total > 0.0 {
+                *center = sum.map(|c| (c / total) as f32);
+            }
+        }
+    }
+    centers
+}
@@ -0,0 +1,37 @@
+use crate::utilities::Image;
+
+/// Resizes an image with a point (nearest-neighbor) filter, keeping hard pixel edges.
+///
+/// Each output pixel takes the source pixel under its center, as ImageMagick's `-filter point`
+/// does.
+pub fn resize_point(image: &Image, width: u32, height: u32) -> Image {
+    let mut out = Image::new(width, height);
+    if image.width == 0 || image.height == 0 {
+        return out;
+    }
+    for y in 0..height {
+        let sy = source_index(y, height, image.height);
+        for x in 0..width {
+            let sx = source_index(x, width, image.width);
+            out.set(x, y, image.get(sx, sy));
+        }
+    }
+    out
+}
+
+/// Point-resizes an image to `height` pixels tall, scaling the width to keep the aspect ratio.
+pub fn pixelate(image: &Image, height: u32) -> Image {
+    let height = height.max(1);
+    let width = if image.height == 0 {
+        0
+    } else {
+        ((image.width as f64 * height as f64 / image.height as f64).round() as u32).max(1)
+    };
+    resize_point(image, width, height)
+}
+
+/// Returns the source pixel whose span covers the center of output pixel `i`.
+fn source_index(i: u32, out_len: u32, src_len: u32) -> u32 {
+    let center = (i as f64 + 0.5) * src_len as f64 / out_len as f64;
+    (center as u32).min(src_len - 1)
+}
@@ -0,0 +1,99 @@
+use crate::{AtlasRect, AtlasSprite, utilities::Image};
+
+/// Returns the cells of a grid laid over a `width` by `height` sheet in reading order. Cells
+/// start `margin` pixels in from the top left, `spacing` pixels apart, and only whole cells are
+/// kept.
+pub fn grid_cells(
+    width: u32,
+    height: u32,
+    cell_width: u32,
+    cell_height: u32,
+    margin: u32,
+    spacing: u32,
+) -> Vec<AtlasRect> {
+    let count = |size: u32, cell: u32| {
+        let usable = size.saturating_sub(margin);
+        if usable < cell {
+            0
+        } else {
+            (usable - cell) / (cell + spacing) + 1
+        }
+    };
+    let (columns, rows) = (count(width, cell_width), count(height, cell_height));
+    (0..rows)
+        .flat_map(|row| {
+            (0..columns).map(move |column| AtlasRect {
+                x: margin + column * (cell_width + spacing),
+                y: margin + row * (cell_height + spacing),
+                width: cell_width,
+                height: cell_height,
+            })
+        })
+        .collect()
+}
+
+/// Returns the bounding boxes of the 8-connected regions of non-transparent pixels in `image`
+/// holding at least `min_pixels` pixels, in reading order by their top-left corners.
+pub fn detect_sprites(image: &Image, min_pixels: usize) -> Vec<AtlasRect> {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let mut seen = vec![false; width * height];
+    let mut regions = Vec::new();
+    let mut stack = Vec::new();
+    for start in 0..width * height {
+        if seen[start] || image.pixels[start][3] == 0 {
+            continue;
+        }
+        seen[start] = true;
+        stack.push(start);
+        let (mut left, mut top) = (usize::MAX, usize::MAX);
+        let (mut right, mut bottom) = (0, 0);
+        let mut pixels = 0;
+        while let Some(index) = stack.pop() {
+            let (x, y) = (index % width, index / width);
+            left = left.min(x);
+            right = right.max(x);
+            top = top.min(y);
+            bottom = bottom.max(y);
+            pixels += 1;
+            for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                    let neighbor = ny * width + nx;
+                    if !seen[neighbor] && image.pixels[neighbor][3] > 0 {
+                        seen[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+        if pixels >= min_pixels {
+            regions.push(AtlasRect {
+                x: left as u32,
+                y: top as u32,
+                width: (right - left + 1) as u32,
+                height: (bottom - top + 1) as u32,
+            });
+        }
+    }
+    regions.sort_by_key(|rect| (rect.y, rect.x));
+    regions
+}
+
+/// Cuts `rect` out of `image`.
+pub fn crop_rect(image: &Image, rect: AtlasRect) -> Image {
+    image.crop(rect.x, rect.y, rect.width, rect.height)
+}
+
+/// Cuts a packed sprite out of its page, turning it back upright and, unless `keep_trim` is
+/// set, restoring any trimmed transparent border so it matches the original image.
+pub fn extract_sprite(page: &Image, sprite: &AtlasSprite, keep_trim: bool) -> Image {
+    let mut image = crop_rect(page, sprite.rect);
+    if sprite.rotated {
+        image = image.rotate_ccw();
+    }
+    if keep_trim {
+        return image;
+    }
+    let mut restored = Image::new(sprite.source_size.width, sprite.source_size.height);
+    restored.blit(&image, sprite.offset.x, sprite.offset.y);
+    restored
+}
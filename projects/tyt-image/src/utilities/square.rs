@@ -0,0 +1,9 @@
+use crate::utilities::Image;
+
+/// Centers an image on a transparent square canvas whose side is its larger dimension.
+pub fn square_image(image: &Image) -> Image {
+    let side = image.width.max(image.height);
+    let mut out = Image::new(side, side);
+    out.blit(image, (side - image.width) / 2, (side - image.height) / 2);
+    out
+}
@@ -0,0 +1,88 @@
+use std::{
+    fs,
+    io::{Error as IOError, ErrorKind, Result},
+    path::{Component, Path, PathBuf},
+};
+
+/// Characters that make a path component a glob pattern rather than a literal name.
+const GLOB_CHARS: &[char] = &['*', '?', '[', '{'];
+
+/// Returns sorted file paths matching a glob pattern, or the files directly inside `pattern` when
+/// it names a directory.
+///
+/// `*` and `?` stay within one path component, while `**` crosses any number of directories.
+pub fn glob_files(pattern: &str) -> Result<Vec<PathBuf>> {
+    let path = Path::new(pattern);
+    if path.is_dir() {
+        let mut paths = Vec::new();
+        walk(path, 1, &mut paths)?;
+        paths.sort();
+        return Ok(paths);
+    }
+
+    let matcher = globset::GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| {
+            IOError::new(
+                ErrorKind::InvalidInput,
+                format!("invalid glob pattern '{pattern}': {e}"),
+            )
+        })?
+        .compile_matcher();
+
+    // Walk from the deepest directory named literally, only as deep as the pattern reaches.
+    let components: Vec<Component> = path.components().collect();
+    let literal = components
+        .iter()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(GLOB_CHARS))
+        .count();
+    if literal == components.len() {
+        return Ok(if path.is_file() {
+            vec![path.to_owned()]
+        } else {
+            Vec::new()
+        });
+    }
+    let root: PathBuf = components[..literal].iter().collect();
+    let depth = if pattern.contains("**") {
+        usize::MAX
+    } else {
+        components.len() - literal
+    };
+
+    let mut paths = Vec::new();
+    walk(&root, depth, &mut paths)?;
+    paths.retain(|path| matcher.is_match(path));
+    paths.sort();
+    Ok(paths)
+}
+
+/// Collects the files up to `depth` levels below `dir`, where an empty `dir` is the current
+/// directory and yields relative paths. A missing `dir` has no files.
+fn walk(dir: &Path, depth: usize, paths: &mut Vec<PathBuf>) -> Result<()> {
+    if depth == 0 {
+        return Ok(());
+    }
+    let read_from = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let entries = match fs::read_dir(read_from) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let path = dir.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            walk(&path, depth - 1, paths)?;
+        } else if path.is_file() {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
@@ -4,8 +4,10 @@ mod create_temp_dir;
 mod exec;
 mod exec_error;
 mod exec_map;
+mod glob_files;
 mod list_dir;
 mod load_image_rgba;
+mod load_image_rgba32f;
 mod match_glob;
 mod mesh_with_uvs;
 mod parse_json;
@@ -15,10 +17,14 @@ mod remove_dir_all;
 mod remove_file;
 mod serialize_json_pretty;
 mod serialize_points_and_colors_json;
+mod serialize_sh9_json;
+mod serialize_sky_analysis_json;
 mod temp_counter_next;
 mod unique_sibling_temp_path;
 mod unique_temp_path;
 mod write_file;
+mod write_image_rgba;
+mod write_image_rgba32f;
 mod write_stdout;
 
 pub use ::serde_json;
@@ -29,8 +35,10 @@ pub use create_temp_dir::*;
 pub use exec::*;
 pub use exec_error::*;
 pub use exec_map::*;
+pub use glob_files::*;
 pub use list_dir::*;
 pub use load_image_rgba::*;
+pub use load_image_rgba32f::*;
 pub use match_glob::*;
 pub use mesh_with_uvs::*;
 pub use parse_json::*;
@@ -40,8 +48,12 @@ pub use remove_dir_all::*;
 pub use remove_file::*;
 pub use serialize_json_pretty::*;
 pub use serialize_points_and_colors_json::*;
+pub use serialize_sh9_json::*;
+pub use serialize_sky_analysis_json::*;
 pub(crate) use temp_counter_next::*;
 pub use unique_sibling_temp_path::*;
 pub use unique_temp_path::*;
 pub use write_file::*;
+pub use write_image_rgba::*;
+pub use write_image_rgba32f::*;
 pub use write_stdout::*;
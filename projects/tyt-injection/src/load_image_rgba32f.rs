@@ -0,0 +1,13 @@
+use std::{
+    io::{Error as IOError, ErrorKind, Result},
+    path::Path,
+};
+
+/// Loads an image from disk and converts it to RGBA32F, returning the pixel data, width, and
+/// height. Integer formats are mapped to `[0, 1]` without any color space conversion.
+pub fn load_image_rgba32f(path: &Path) -> Result<(Vec<f32>, u32, u32)> {
+    let img = image::open(path).map_err(|e| IOError::new(ErrorKind::InvalidData, e))?;
+    let rgba = img.into_rgba32f();
+    let (w, h) = (rgba.width(), rgba.height());
+    Ok((rgba.into_raw(), w, h))
+}
@@ -0,0 +1,18 @@
+use serde::Serialize;
+use std::io::{Error as IOError, ErrorKind, Result};
+
+#[derive(Serialize)]
+struct Sh9<'a> {
+    coefficients: &'a [[f64; 3]; 9],
+}
+
+/// Serializes nine L2 spherical harmonics RGB coefficients to pretty JSON bytes.
+pub fn serialize_sh9_json(coefficients: &[[f64; 3]; 9]) -> Result<Vec<u8>> {
+    let data = Sh9 { coefficients };
+
+    let mut bytes =
+        serde_json::to_vec_pretty(&data).map_err(|e| IOError::new(ErrorKind::InvalidData, e))?;
+    bytes.push(b'\n');
+
+    Ok(bytes)
+}
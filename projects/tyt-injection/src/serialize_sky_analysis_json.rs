@@ -0,0 +1,50 @@
+use serde::Serialize;
+use std::io::{Error as IOError, ErrorKind, Result};
+use ty_math::{TyRgbaColor, TyVector3};
+use ty_math_serde::{TyRgbaColorSerde, TyVector3Serde};
+
+#[derive(Serialize)]
+struct SkyAnalysis {
+    sun: Sun,
+    zenith: TyRgbaColorSerde,
+    horizon: TyRgbaColorSerde,
+    ground: TyRgbaColorSerde,
+    ambient: TyRgbaColorSerde,
+}
+
+#[derive(Serialize)]
+struct Sun {
+    direction: TyVector3Serde,
+    intensity: f64,
+    color: TyRgbaColorSerde,
+}
+
+/// Serializes a sky's sun and its zenith, horizon, ground, and ambient colors to pretty JSON
+/// bytes.
+pub fn serialize_sky_analysis_json(
+    sun_direction: TyVector3,
+    sun_intensity: f64,
+    sun_color: TyRgbaColor,
+    zenith: TyRgbaColor,
+    horizon: TyRgbaColor,
+    ground: TyRgbaColor,
+    ambient: TyRgbaColor,
+) -> Result<Vec<u8>> {
+    let data = SkyAnalysis {
+        sun: Sun {
+            direction: sun_direction.into(),
+            intensity: sun_intensity,
+            color: sun_color.into(),
+        },
+        zenith: zenith.into(),
+        horizon: horizon.into(),
+        ground: ground.into(),
+        ambient: ambient.into(),
+    };
+
+    let mut bytes =
+        serde_json::to_vec_pretty(&data).map_err(|e| IOError::new(ErrorKind::InvalidData, e))?;
+    bytes.push(b'\n');
+
+    Ok(bytes)
+}
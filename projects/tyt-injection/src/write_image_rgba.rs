@@ -0,0 +1,28 @@
+use image::{DynamicImage, ImageFormat, RgbaImage};
+use std::{
+    io::{Error as IOError, ErrorKind, Result},
+    path::Path,
+};
+
+/// Writes RGBA8 pixel data to disk, inferring the image format from the path's extension.
+///
+/// Formats without an alpha channel, such as JPEG, drop alpha. Float formats, such as OpenEXR,
+/// store the pixels in `[0, 1]`.
+pub fn write_image_rgba(path: &Path, pixels: &[u8], width: u32, height: u32) -> Result<()> {
+    let buffer = RgbaImage::from_raw(width, height, pixels.to_vec()).ok_or_else(|| {
+        IOError::new(
+            ErrorKind::InvalidInput,
+            format!("pixel data does not match {width}x{height} RGBA8 image"),
+        )
+    })?;
+    let img = DynamicImage::ImageRgba8(buffer);
+    let img = match ImageFormat::from_path(path) {
+        Ok(ImageFormat::Jpeg) => DynamicImage::ImageRgb8(img.into_rgb8()),
+        Ok(ImageFormat::Farbfeld) => DynamicImage::ImageRgba16(img.into_rgba16()),
+        Ok(ImageFormat::OpenExr) => DynamicImage::ImageRgba32F(img.into_rgba32f()),
+        Ok(ImageFormat::Hdr) => DynamicImage::ImageRgb32F(img.into_rgb32f()),
+        _ => img,
+    };
+    img.save(path)
+        .map_err(|e| IOError::new(ErrorKind::InvalidData, e))
+}
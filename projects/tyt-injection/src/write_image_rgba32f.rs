@@ -0,0 +1,24 @@
+use image::{DynamicImage, ImageFormat, Rgba32FImage};
+use std::{
+    io::{Error as IOError, ErrorKind, Result},
+    path::Path,
+};
+
+/// Writes RGBA32F pixel data to disk, inferring the image format from the path's extension.
+///
+/// Formats without an alpha channel, such as Radiance `.hdr`, drop alpha.
+pub fn write_image_rgba32f(path: &Path, pixels: &[f32], width: u32, height: u32) -> Result<()> {
+    let buffer = Rgba32FImage::from_raw(width, height, pixels.to_vec()).ok_or_else(|| {
+        IOError::new(
+            ErrorKind::InvalidInput,
+            format!("pixel data does not match {width}x{height} RGBA32F image"),
+        )
+    })?;
+    let img = DynamicImage::ImageRgba32F(buffer);
+    let img = match ImageFormat::from_path(path) {
+        Ok(ImageFormat::Hdr) => DynamicImage::ImageRgb32F(img.into_rgb32f()),
+        _ => img,
+    };
+    img.save(path)
+        .map_err(|e| IOError::new(ErrorKind::InvalidData, e))
+}